@@ -0,0 +1,7 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS search.business_redirects;
+DROP TABLE IF EXISTS search.business_duplicate_candidates;
+DROP INDEX IF EXISTS search.idx_businesses_normalized_name_trgm;
+DROP FUNCTION IF EXISTS search.normalize_name(TEXT);
+DROP FUNCTION IF EXISTS search.transliterate_bg(TEXT);
+DROP EXTENSION IF EXISTS pg_trgm;
@@ -0,0 +1,54 @@
+-- Add up migration script here
+CREATE EXTENSION IF NOT EXISTS pg_trgm SCHEMA search;
+
+-- Transliterate Bulgarian Cyrillic to Latin (Streamlined System) so that
+-- names written in either script can be compared with each other.
+CREATE OR REPLACE FUNCTION search.transliterate_bg(input TEXT)
+RETURNS TEXT AS $$
+    SELECT translate(
+        replace(replace(replace(replace(replace(replace(replace(
+            lower(input),
+            'щ', 'sht'), 'ж', 'zh'), 'ц', 'ts'), 'ч', 'ch'), 'ш', 'sh'), 'ю', 'yu'), 'я', 'ya'),
+        'абвгдезийклмнопрстуфхъь',
+        'abvgdeziyklmnoprstufhay'
+    );
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
+
+-- Lowercased, transliterated name with punctuation collapsed to single spaces
+CREATE OR REPLACE FUNCTION search.normalize_name(input TEXT)
+RETURNS TEXT AS $$
+    SELECT btrim(regexp_replace(search.transliterate_bg(input), '[^a-z0-9]+', ' ', 'g'));
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
+
+CREATE INDEX idx_businesses_normalized_name_trgm
+    ON search.businesses USING GIN (search.normalize_name(name) search.gin_trgm_ops);
+
+-- Pairs of businesses flagged as probable duplicates (business_id < duplicate_id)
+CREATE TABLE search.business_duplicate_candidates (
+    business_id UUID NOT NULL REFERENCES search.businesses(id) ON DELETE CASCADE,
+    duplicate_id UUID NOT NULL REFERENCES search.businesses(id) ON DELETE CASCADE,
+    distance_meters DOUBLE PRECISION NOT NULL,
+    name_similarity REAL NOT NULL,
+    shared_categories search.business_category[] NOT NULL DEFAULT '{}',
+    detected_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (business_id, duplicate_id),
+    CONSTRAINT ordered_duplicate_pair CHECK (business_id < duplicate_id)
+);
+
+CREATE INDEX idx_business_duplicate_candidates_duplicate_id
+    ON search.business_duplicate_candidates (duplicate_id);
+
+-- Ids retired by a merge or claim, pointing at the business that replaced them.
+-- retired_osm_id keeps the OSM identity of a merged object the surviving row
+-- could not take over, so the sync doesn't re-create it.
+CREATE TABLE search.business_redirects (
+    retired_id UUID PRIMARY KEY,
+    business_id UUID NOT NULL REFERENCES search.businesses(id) ON DELETE CASCADE,
+    retired_osm_id BIGINT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_business_redirects_business_id ON search.business_redirects (business_id);
+CREATE UNIQUE INDEX idx_business_redirects_retired_osm_id
+    ON search.business_redirects (retired_osm_id)
+    WHERE retired_osm_id IS NOT NULL;
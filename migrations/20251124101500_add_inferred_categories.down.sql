@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE search.businesses
+  DROP COLUMN IF EXISTS inferred_categories;
@@ -0,0 +1,4 @@
+-- Add up migration script here
+-- Categories assigned from name keywords only, without a supporting OSM tag
+ALTER TABLE search.businesses
+  ADD COLUMN inferred_categories search.business_category[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,8 @@
+-- Add up migration script here
+ALTER TYPE search.business_category ADD VALUE 'motorcycle_shop';
+ALTER TYPE search.business_category ADD VALUE 'truck_repair';
+ALTER TYPE search.business_category ADD VALUE 'towing';
+ALTER TYPE search.business_category ADD VALUE 'auto_parts';
+ALTER TYPE search.business_category ADD VALUE 'auto_glass';
+ALTER TYPE search.business_category ADD VALUE 'car_audio';
+ALTER TYPE search.business_category ADD VALUE 'driving_school';
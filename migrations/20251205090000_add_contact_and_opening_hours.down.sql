@@ -0,0 +1,8 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS search.idx_businesses_updated_at;
+
+ALTER TABLE search.businesses
+  DROP COLUMN IF EXISTS phone,
+  DROP COLUMN IF EXISTS email,
+  DROP COLUMN IF EXISTS website,
+  DROP COLUMN IF EXISTS opening_hours;
@@ -0,0 +1,8 @@
+-- Add up migration script here
+ALTER TABLE search.businesses
+  ADD COLUMN phone TEXT,
+  ADD COLUMN email TEXT,
+  ADD COLUMN website TEXT,
+  ADD COLUMN opening_hours TEXT;
+
+CREATE INDEX idx_businesses_updated_at ON search.businesses (updated_at);
@@ -0,0 +1,18 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS search.idx_businesses_search_text_fts;
+DROP INDEX IF EXISTS search.idx_businesses_search_text_trgm;
+DROP FUNCTION IF EXISTS search.business_search_text(TEXT, TEXT, TEXT, TEXT, TEXT[]);
+
+-- Restore the transliteration from 20251120093000_add_duplicate_detection
+CREATE OR REPLACE FUNCTION search.transliterate_bg(input TEXT)
+RETURNS TEXT AS $$
+    SELECT translate(
+        replace(replace(replace(replace(replace(replace(replace(
+            lower(input),
+            'щ', 'sht'), 'ж', 'zh'), 'ц', 'ts'), 'ч', 'ch'), 'ш', 'sh'), 'ю', 'yu'), 'я', 'ya'),
+        'абвгдезийклмнопрстуфхъь',
+        'abvgdeziyklmnoprstufhay'
+    );
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
+
+REINDEX INDEX search.idx_businesses_normalized_name_trgm;
@@ -0,0 +1,39 @@
+-- Add up migration script here
+-- Word-final "ия" is transliterated as "ia" (София -> sofia), matching the
+-- official spelling of place names in OSM and on signs.
+CREATE OR REPLACE FUNCTION search.transliterate_bg(input TEXT)
+RETURNS TEXT AS $$
+    SELECT translate(
+        replace(replace(replace(replace(replace(replace(replace(
+            regexp_replace(lower(input), 'ия\M', 'ia', 'g'),
+            'щ', 'sht'), 'ж', 'zh'), 'ц', 'ts'), 'ч', 'ch'), 'ш', 'sh'), 'ю', 'yu'), 'я', 'ya'),
+        'абвгдезийклмнопрстуфхъь',
+        'abvgdeziyklmnoprstufhay'
+    );
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
+
+REINDEX INDEX search.idx_businesses_normalized_name_trgm;
+
+-- Transliterated text of everything a business can be searched by. Declared
+-- immutable so it can be indexed (array_to_string is only marked stable).
+CREATE OR REPLACE FUNCTION search.business_search_text(
+    name TEXT,
+    name_en TEXT,
+    address TEXT,
+    city TEXT,
+    specializations TEXT[]
+)
+RETURNS TEXT AS $$
+    SELECT search.normalize_name(concat_ws(' ',
+        name, name_en, address, city,
+        replace(array_to_string(specializations, ' '), '_', ' ')
+    ));
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
+
+CREATE INDEX idx_businesses_search_text_trgm ON search.businesses USING GIN (
+    search.business_search_text(name, name_en, address, city, specializations) search.gin_trgm_ops
+);
+
+CREATE INDEX idx_businesses_search_text_fts ON search.businesses USING GIN (
+    to_tsvector('simple', search.business_search_text(name, name_en, address, city, specializations))
+);
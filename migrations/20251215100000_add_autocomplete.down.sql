@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP MATERIALIZED VIEW IF EXISTS search.autocomplete_terms;
+DROP INDEX IF EXISTS search.idx_businesses_normalized_name_prefix;
@@ -0,0 +1,32 @@
+-- Add up migration script here
+-- Prefix lookups on business names; word prefixes inside a name use the
+-- trigram index on the same expression
+CREATE INDEX idx_businesses_normalized_name_prefix
+    ON search.businesses (search.normalize_name(name) text_pattern_ops);
+
+-- Cities and specializations offered as autocomplete suggestions, with their
+-- most common spelling. Refreshed after every sync.
+CREATE MATERIALIZED VIEW search.autocomplete_terms AS
+SELECT
+    'city' AS kind,
+    mode() WITHIN GROUP (ORDER BY btrim(city)) AS term,
+    search.normalize_name(city) AS normalized,
+    COUNT(*) AS business_count
+FROM search.businesses
+WHERE search.normalize_name(city) <> ''
+GROUP BY search.normalize_name(city)
+UNION ALL
+SELECT
+    'specialization' AS kind,
+    mode() WITHIN GROUP (ORDER BY specialization) AS term,
+    search.normalize_name(specialization) AS normalized,
+    COUNT(*) AS business_count
+FROM search.businesses
+CROSS JOIN LATERAL unnest(specializations) AS specialization
+WHERE search.normalize_name(specialization) <> ''
+GROUP BY search.normalize_name(specialization);
+
+CREATE UNIQUE INDEX idx_autocomplete_terms_kind_normalized
+    ON search.autocomplete_terms (kind, normalized);
+CREATE INDEX idx_autocomplete_terms_normalized_prefix
+    ON search.autocomplete_terms (normalized text_pattern_ops);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS search.idx_businesses_review_count;
+DROP INDEX IF EXISTS search.idx_businesses_average_reviews;
+DROP INDEX IF EXISTS search.idx_businesses_registered_location;
+DROP INDEX IF EXISTS search.idx_businesses_specializations;
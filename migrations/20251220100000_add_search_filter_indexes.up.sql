@@ -0,0 +1,10 @@
+-- Add up migration script here
+CREATE INDEX idx_businesses_specializations ON search.businesses USING GIN (specializations);
+
+-- Registered-only searches walk a much smaller spatial index
+CREATE INDEX idx_businesses_registered_location
+  ON search.businesses USING GIST (location)
+  WHERE is_registered;
+
+CREATE INDEX idx_businesses_average_reviews ON search.businesses (average_reviews);
+CREATE INDEX idx_businesses_review_count ON search.businesses (review_count);
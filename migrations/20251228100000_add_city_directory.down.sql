@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP MATERIALIZED VIEW IF EXISTS search.cities;
+DROP INDEX IF EXISTS search.idx_businesses_city_slug;
+DROP FUNCTION IF EXISTS search.city_slug(TEXT);
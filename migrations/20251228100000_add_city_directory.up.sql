@@ -0,0 +1,50 @@
+-- Add up migration script here
+-- URL slug of a city name in either script, e.g. "Велико Търново" and
+-- "Veliko Tarnovo" both give "veliko-tarnovo"; NULL for blank names
+CREATE OR REPLACE FUNCTION search.city_slug(city TEXT)
+RETURNS TEXT AS $$
+    SELECT NULLIF(replace(search.normalize_name(city), ' ', '-'), '');
+$$ LANGUAGE SQL IMMUTABLE PARALLEL SAFE;
+
+CREATE INDEX idx_businesses_city_slug ON search.businesses (search.city_slug(city));
+
+-- Every city with businesses, its spellings, centroid and business counts.
+-- Refreshed after every sync.
+CREATE MATERIALIZED VIEW search.cities AS
+WITH located AS (
+    SELECT
+        search.city_slug(city) AS slug,
+        btrim(city) AS spelling,
+        btrim(city) ~ '[А-Яа-я]' AS cyrillic,
+        location,
+        categories
+    FROM search.businesses
+    WHERE search.city_slug(city) IS NOT NULL
+),
+category_counts AS (
+    SELECT slug, jsonb_object_agg(category, business_count) AS category_counts
+    FROM (
+        SELECT slug, category::text AS category, COUNT(*) AS business_count
+        FROM located
+        CROSS JOIN LATERAL unnest(categories) AS category
+        GROUP BY slug, category
+    ) per_category
+    GROUP BY slug
+)
+SELECT
+    located.slug,
+    mode() WITHIN GROUP (ORDER BY spelling) AS name,
+    mode() WITHIN GROUP (ORDER BY spelling) FILTER (WHERE cyrillic) AS name_cyrillic,
+    COALESCE(
+        mode() WITHIN GROUP (ORDER BY spelling) FILTER (WHERE NOT cyrillic),
+        initcap(replace(located.slug, '-', ' '))
+    ) AS name_latin,
+    ST_Y(ST_Centroid(ST_Collect(location))) AS latitude,
+    ST_X(ST_Centroid(ST_Collect(location))) AS longitude,
+    COUNT(*) AS business_count,
+    COALESCE(category_counts.category_counts, '{}') AS category_counts
+FROM located
+LEFT JOIN category_counts ON category_counts.slug = located.slug
+GROUP BY located.slug, category_counts.category_counts;
+
+CREATE UNIQUE INDEX idx_cities_slug ON search.cities (slug);
@@ -0,0 +1,5 @@
+-- Add up migration script here
+-- Categories a business took over from duplicates merged into it. The Overpass
+-- sync replaces `categories` with the OSM ones and adds these back.
+ALTER TABLE search.businesses
+  ADD COLUMN merged_categories search.business_category[] NOT NULL DEFAULT '{}';
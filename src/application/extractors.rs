@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        path::ErrorKind,
+        rejection::PathRejection,
+        FromRequest, FromRequestParts, Path, RawPathParams, Request,
+    },
+    http::{header, request::Parts},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    application::{state::AppState, validation::Validate},
+    domain::entities::{
+        business::{Business, BusinessResponse},
+        geojson::{FeatureCollection, GEOJSON_CONTENT_TYPE},
+    },
+    shared::error::AppError,
+};
+
+/// Query string deserialized into `T` and checked with `Validate`. Unlike
+/// axum's `Query`, failures are structured 400s naming the bad parameter.
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T> FromRequestParts<Arc<AppState>> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let value: T = deserialize_query(parts.uri.query().unwrap_or_default())?;
+        value.validate(&state.config)?;
+        Ok(Self(value))
+    }
+}
+
+/// JSON body deserialized into `T` and checked with `Validate`
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> FromRequest<Arc<AppState>> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state)
+            .await
+            .map_err(|rejection| AppError::validation("body", rejection.body_text()))?;
+        value.validate(&state.config)?;
+        Ok(Self(value))
+    }
+}
+
+/// Path parameters deserialized into `T`. Unlike axum's `Path`, failures are
+/// structured 400s naming the bad parameter.
+pub struct ValidatedPath<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedPath<T>
+where
+    T: DeserializeOwned + Send,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let rejection = match Path::<T>::from_request_parts(parts, state).await {
+            Ok(Path(value)) => return Ok(Self(value)),
+            Err(rejection) => rejection,
+        };
+
+        let key = match &rejection {
+            PathRejection::FailedToDeserializePathParams(err) => match err.kind() {
+                ErrorKind::ParseErrorAtKey { key, .. }
+                | ErrorKind::InvalidUtf8InPathParam { key }
+                | ErrorKind::DeserializeError { key, .. } => Some(key.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        // Errors from a single value, e.g. an unknown enum variant, carry no key
+        let field = match key {
+            Some(key) => key,
+            None => single_path_param(parts, state).await.unwrap_or_else(|| "path".to_string()),
+        };
+
+        Err(AppError::validation(&field, rejection.body_text()))
+    }
+}
+
+/// Name of the route's only path parameter, None if it has several
+async fn single_path_param<S: Send + Sync>(parts: &mut Parts, state: &S) -> Option<String> {
+    let params = RawPathParams::from_request_parts(parts, state).await.ok()?;
+    let mut keys = params.iter().map(|(key, _)| key);
+    match (keys.next(), keys.next()) {
+        (Some(key), None) => Some(key.to_string()),
+        _ => None,
+    }
+}
+
+fn deserialize_query<T: DeserializeOwned>(query: &str) -> Result<T, AppError> {
+    let deserializer =
+        serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        let message = err.inner().to_string();
+        // Missing fields are reported at the root, with the name in the message
+        let field = match err.path().to_string() {
+            path if path != "." => path,
+            _ => message
+                .strip_prefix("missing field `")
+                .and_then(|rest| rest.split('`').next())
+                .unwrap_or("query")
+                .to_string(),
+        };
+        AppError::validation(&field, message)
+    })
+}
+
+/// Output format of a search endpoint, chosen with `format=json|geojson` or,
+/// without the parameter, an `Accept: application/geo+json` header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseFormat {
+    #[default]
+    Json,
+    GeoJson,
+}
+
+#[derive(Deserialize)]
+struct FormatParam {
+    format: Option<ResponseFormat>,
+}
+
+impl<S> FromRequestParts<S> for ResponseFormat
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let param: FormatParam = deserialize_query(parts.uri.query().unwrap_or_default())?;
+        if let Some(format) = param.format {
+            return Ok(format);
+        }
+
+        let accepts_geojson = parts.headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .any(|value| value.contains(GEOJSON_CONTENT_TYPE));
+
+        Ok(if accepts_geojson { Self::GeoJson } else { Self::Json })
+    }
+}
+
+impl ResponseFormat {
+    /// Businesses as a JSON array of `BusinessResponse` or a GeoJSON FeatureCollection
+    pub fn businesses(self, businesses: Vec<Business>) -> Response {
+        match self {
+            Self::Json => {
+                let responses: Vec<BusinessResponse> =
+                    businesses.into_iter().map(|b| b.to_response()).collect();
+                Json(responses).into_response()
+            }
+            Self::GeoJson => geojson(businesses.into_iter().collect()),
+        }
+    }
+}
+
+/// Serializes a FeatureCollection with the GeoJSON media type
+pub fn geojson<P: Serialize>(collection: FeatureCollection<P>) -> Response {
+    ([(header::CONTENT_TYPE, GEOJSON_CONTENT_TYPE)], Json(collection)).into_response()
+}
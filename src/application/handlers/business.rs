@@ -1,13 +1,15 @@
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::sync::Arc;
-use crate::{application::state::AppState, domain::entities::{business::BusinessResponse, category::BusinessCategory}};
+use crate::{application::{extractors::{geojson, ResponseFormat, ValidatedJson, ValidatedPath, ValidatedQuery}, state::AppState, validation::{self, Validate}}, config::config::Config, domain::entities::{area::AreaSearchQuery, autocomplete::AutocompleteSuggestion, business::{Business, BusinessDetailsResponse, BusinessResponse, MAX_BUSINESS_BATCH}, category::BusinessCategory, cluster::ClusteredBusinessesResponse, geojson::{Feature, FeatureCollection, Geometry}, route::{decode_polyline, RouteSearchQuery, MAX_ROUTE_CORRIDOR_METERS, MAX_ROUTE_POINTS}, search::{comma_separated, AttributeFilter, BoundingBox, CategoryFilter, MatchMode, NearestByCategoryQuery, NearestSearchQuery, RadiusSearchQuery, RankingWeights, SearchArea, SearchCursor, SearchFacets, SearchPage, SortOrder, TextSearchQuery, MAX_NEAREST_PER_CATEGORY}, sync::SyncStats, tile::MAX_TILE_ZOOM}, shared::error::AppError};
+use crate::domain::services::business_service::{MAX_AREA_RESULTS, MAX_BBOX_RESULTS};
 
 #[derive(Debug, Deserialize)]
 pub struct SyncRequest {
@@ -18,17 +20,459 @@ pub struct SyncRequest {
 pub struct SyncResponse {
     pub businesses_synced: usize,
     pub message: String,
+    pub statistics: SyncStats,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BusinessBatchRequest {
+    pub ids: Vec<Uuid>,
+}
+
+impl Validate for BusinessBatchRequest {
+    fn validate(&self, _config: &Config) -> Result<(), AppError> {
+        if self.ids.is_empty() || self.ids.len() > MAX_BUSINESS_BATCH {
+            return Err(AppError::validation(
+                "ids",
+                format!("give between 1 and {} ids", MAX_BUSINESS_BATCH),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BusinessBatchResponse {
+    pub businesses: Vec<BusinessDetailsResponse>,
+    /// Requested ids with no business
+    pub missing: Vec<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SearchByRadiusAndCategoryQuery {
     pub latitude: f64,
     pub longitude: f64,
-    pub radius_km: i32,
+    /// Search radius in kilometers; fractions allowed. Give this or `radius_m`.
+    pub radius_km: Option<f64>,
+    /// Search radius in meters
+    pub radius_m: Option<f64>,
+    /// Single category, kept for older clients
+    pub category: Option<BusinessCategory>,
+    /// Comma-separated categories, e.g. `CarWash,GasStation`
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub categories: Option<Vec<BusinessCategory>>,
+    /// Whether businesses need `any` (default) or `all` of the categories
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    /// Minimum average review score
+    pub min_rating: Option<f64>,
+    pub min_reviews: Option<i32>,
+    #[serde(default)]
+    pub registered_only: bool,
+    /// Comma-separated specializations, e.g. `chip_tuning,body_repair`
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub specializations: Option<Vec<String>>,
+    /// Whether businesses need `any` (default) or `all` of the specializations
+    #[serde(default)]
+    pub specializations_match: MatchMode,
+    pub limit: Option<i64>,
+    #[serde(default)]
+    pub sort: SortOrder,
+    /// Also return counts per category, specialization and city; in the
+    /// envelope and GeoJSON responses only
+    #[serde(default)]
+    pub facets: bool,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+}
+
+/// Headers carrying the paging state of radius searches answered with a plain array
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+pub const TOTAL_ESTIMATE_HEADER: &str = "x-total-estimate";
+
+/// Envelope of `/businesses/search/radius`
+#[derive(Debug, Serialize)]
+pub struct RadiusSearchResponse {
+    pub businesses: Vec<BusinessResponse>,
+    /// Pass as `cursor` to load the next page; null on the last page
+    pub next_cursor: Option<String>,
+    /// Matches across all pages, counted up to 10,000
+    pub total_estimate: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets: Option<SearchFacets>,
+}
+
+impl SearchByRadiusAndCategoryQuery {
+    pub fn radius_meters(&self) -> Option<f64> {
+        self.radius_m.or(self.radius_km.map(|km| km * 1000.0))
+    }
+
+    pub fn category_filter(&self) -> CategoryFilter {
+        let categories = self.categories
+            .iter()
+            .flatten()
+            .chain(self.category.iter())
+            .cloned()
+            .collect();
+
+        CategoryFilter::new(categories, self.match_mode)
+    }
+
+    pub fn attribute_filter(&self) -> AttributeFilter {
+        AttributeFilter {
+            min_rating: self.min_rating,
+            min_reviews: self.min_reviews,
+            registered_only: self.registered_only,
+            specializations: self.specializations.clone().unwrap_or_default(),
+            specialization_mode: self.specializations_match,
+        }
+    }
+}
+
+impl Validate for SearchByRadiusAndCategoryQuery {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        validation::latitude("latitude", self.latitude)?;
+        validation::longitude("longitude", self.longitude)?;
+
+        let (field, radius) = match (self.radius_km, self.radius_m) {
+            (Some(_), Some(_)) => {
+                return Err(AppError::validation("radius_km", "give either radius_km or radius_m, not both"));
+            }
+            (Some(km), None) => ("radius_km", km * 1000.0),
+            (None, Some(m)) => ("radius_m", m),
+            (None, None) => return Err(AppError::validation("radius_km", "radius_km or radius_m is required")),
+        };
+        let max_radius_meters = config.max_search_radius_km * 1000.0;
+        if !(radius > 0.0 && radius <= max_radius_meters) {
+            return Err(AppError::validation(
+                field,
+                format!("radius must be positive and at most {} km", config.max_search_radius_km),
+            ));
+        }
+
+        if self.category_filter().is_empty() {
+            return Err(AppError::validation("categories", "at least one category is required"));
+        }
+        validation::attributes(self.min_rating, self.min_reviews)?;
+        validation::limit(self.limit, config)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NearestSearchParams {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Comma-separated categories, e.g. `CarWash,GasStation`
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub categories: Option<Vec<BusinessCategory>>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    /// Optional hard cap on the distance; unlimited when omitted
+    pub max_distance_km: Option<f64>,
+    pub limit: Option<i64>,
+}
+
+impl Validate for NearestSearchParams {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        validation::latitude("latitude", self.latitude)?;
+        validation::longitude("longitude", self.longitude)?;
+        if let Some(km) = self.max_distance_km {
+            if !(km > 0.0 && km.is_finite()) {
+                return Err(AppError::validation("max_distance_km", "must be positive"));
+            }
+        }
+        validation::limit(self.limit, config)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NearestByCategoryParams {
+    pub lat: f64,
+    pub lon: f64,
+    /// Comma-separated categories, e.g. `GasStation,CarWash,TireShop`
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub categories: Option<Vec<BusinessCategory>>,
+    /// Businesses returned per category; defaults to 1
+    pub per_category: Option<i64>,
+    /// Optional hard cap on the distance; unlimited when omitted
+    pub max_distance_km: Option<f64>,
+}
+
+impl NearestByCategoryParams {
+    /// Requested categories in order, without repeats
+    pub fn categories(&self) -> Vec<BusinessCategory> {
+        let mut categories: Vec<BusinessCategory> = Vec::new();
+        for category in self.categories.iter().flatten() {
+            if !categories.contains(category) {
+                categories.push(category.clone());
+            }
+        }
+        categories
+    }
+}
+
+impl Validate for NearestByCategoryParams {
+    fn validate(&self, _config: &Config) -> Result<(), AppError> {
+        validation::latitude("lat", self.lat)?;
+        validation::longitude("lon", self.lon)?;
+        if self.categories().is_empty() {
+            return Err(AppError::validation("categories", "at least one category is required"));
+        }
+        if let Some(per_category) = self.per_category {
+            validation::in_range("per_category", per_category, 1, MAX_NEAREST_PER_CATEGORY)?;
+        }
+        if let Some(km) = self.max_distance_km {
+            if !(km > 0.0 && km.is_finite()) {
+                return Err(AppError::validation("max_distance_km", "must be positive"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CategoryNearestResponse {
     pub category: BusinessCategory,
+    pub businesses: Vec<BusinessResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NearestByCategoryResponse {
+    /// One entry per requested category, in request order
+    pub categories: Vec<CategoryNearestResponse>,
+}
+
+/// GeoJSON properties of a nearest-by-category result: the business plus the
+/// requested category it was found for
+#[derive(Debug, Serialize)]
+pub struct CategoryFeatureProperties {
+    pub category: BusinessCategory,
+    #[serde(flatten)]
+    pub business: BusinessResponse,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TextSearchParams {
+    pub q: String,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub categories: Option<Vec<BusinessCategory>>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    pub limit: Option<i64>,
+}
+
+impl Validate for TextSearchParams {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        if self.q.trim().chars().count() < 2 {
+            return Err(AppError::validation("q", "must be at least 2 characters"));
+        }
+        validation::optional_location(self.lat, self.lon)?;
+        validation::limit(self.limit, config)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BboxSearchParams {
+    pub min_lat: f64,
+    pub min_lon: f64,
+    pub max_lat: f64,
+    pub max_lon: f64,
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub categories: Option<Vec<BusinessCategory>>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    /// Minimum average review score
+    pub min_rating: Option<f64>,
+    pub min_reviews: Option<i32>,
+    #[serde(default)]
+    pub registered_only: bool,
+    /// Comma-separated specializations, e.g. `chip_tuning,body_repair`
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub specializations: Option<Vec<String>>,
+    /// Whether businesses need `any` (default) or `all` of the specializations
+    #[serde(default)]
+    pub specializations_match: MatchMode,
+    pub limit: Option<i64>,
+    /// Also return counts per category, specialization and city
+    #[serde(default)]
+    pub facets: bool,
+}
+
+impl BboxSearchParams {
+    pub fn bbox(&self) -> BoundingBox {
+        BoundingBox {
+            min_lat: self.min_lat,
+            min_lon: self.min_lon,
+            max_lat: self.max_lat,
+            max_lon: self.max_lon,
+        }
+    }
+
+    pub fn attribute_filter(&self) -> AttributeFilter {
+        AttributeFilter {
+            min_rating: self.min_rating,
+            min_reviews: self.min_reviews,
+            registered_only: self.registered_only,
+            specializations: self.specializations.clone().unwrap_or_default(),
+            specialization_mode: self.specializations_match,
+        }
+    }
+}
+
+impl Validate for BboxSearchParams {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        validation::bounding_box(&self.bbox())?;
+        validation::attributes(self.min_rating, self.min_reviews)?;
+        validation::limit(self.limit, config)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BboxSearchResponse {
+    pub businesses: Vec<BusinessResponse>,
+    /// The viewport holds more businesses than were returned; zoom in to see all
+    pub truncated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facets: Option<SearchFacets>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RouteSearchRequest {
+    /// Route as an encoded polyline. Give this or `geometry`.
+    pub polyline: Option<String>,
+    /// Decimal places of the polyline: 5 (default, Google) or 6 (OSRM, Valhalla)
+    pub polyline_precision: Option<u32>,
+    /// Route as a GeoJSON LineString
+    pub geometry: Option<Geometry>,
+    /// Maximum distance from the route in meters
+    pub corridor_m: Option<f64>,
+    #[serde(default)]
+    pub categories: Vec<BusinessCategory>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    pub limit: Option<i64>,
+}
+
+impl RouteSearchRequest {
+    /// Route vertices as `[longitude, latitude]`
+    pub fn route(&self) -> Result<Vec<[f64; 2]>, AppError> {
+        match (&self.polyline, &self.geometry) {
+            (Some(polyline), None) => {
+                let precision = self.polyline_precision.unwrap_or(5);
+                if !matches!(precision, 5 | 6) {
+                    return Err(AppError::validation("polyline_precision", "must be 5 or 6"));
+                }
+                decode_polyline(polyline, precision)
+                    .ok_or_else(|| AppError::validation("polyline", "not a valid encoded polyline"))
+            }
+            (None, Some(Geometry::LineString { coordinates })) => Ok(coordinates.clone()),
+            (None, Some(_)) => Err(AppError::validation("geometry", "must be a LineString")),
+            (Some(_), Some(_)) => Err(AppError::validation("polyline", "give either polyline or geometry, not both")),
+            (None, None) => Err(AppError::validation("polyline", "polyline or geometry is required")),
+        }
+    }
+}
+
+impl Validate for RouteSearchRequest {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        let route = self.route()?;
+        let field = if self.polyline.is_some() { "polyline" } else { "geometry" };
+        if !(2..=MAX_ROUTE_POINTS).contains(&route.len()) {
+            return Err(AppError::validation(
+                field,
+                format!("route must have between 2 and {} points", MAX_ROUTE_POINTS),
+            ));
+        }
+        for [lon, lat] in &route {
+            validation::longitude(field, *lon)?;
+            validation::latitude(field, *lat)?;
+        }
+
+        if let Some(corridor) = self.corridor_m {
+            if !(corridor > 0.0 && corridor <= MAX_ROUTE_CORRIDOR_METERS) {
+                return Err(AppError::validation(
+                    "corridor_m",
+                    format!("must be positive and at most {} m", MAX_ROUTE_CORRIDOR_METERS),
+                ));
+            }
+        }
+        validation::limit(self.limit, config)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AreaSearchRequest {
+    /// GeoJSON Polygon or MultiPolygon
+    pub geometry: Geometry,
+    #[serde(default)]
+    pub categories: Vec<BusinessCategory>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    pub limit: Option<i64>,
+}
+
+impl Validate for AreaSearchRequest {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        validation::polygon("geometry", &self.geometry)?;
+        validation::limit(self.limit, config)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AreaSearchResponse {
+    pub businesses: Vec<BusinessResponse>,
+    /// The area holds more matching businesses than were returned
+    pub truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterParams {
+    pub min_lat: f64,
+    pub min_lon: f64,
+    pub max_lat: f64,
+    pub max_lon: f64,
+    /// Web map zoom level of the viewport
+    pub zoom: u8,
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub categories: Option<Vec<BusinessCategory>>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+}
+
+impl ClusterParams {
+    pub fn bbox(&self) -> BoundingBox {
+        BoundingBox {
+            min_lat: self.min_lat,
+            min_lon: self.min_lon,
+            max_lat: self.max_lat,
+            max_lon: self.max_lon,
+        }
+    }
+}
+
+impl Validate for ClusterParams {
+    fn validate(&self, _config: &Config) -> Result<(), AppError> {
+        validation::bounding_box(&self.bbox())?;
+        validation::in_range("zoom", u32::from(self.zoom), 0, MAX_TILE_ZOOM)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AutocompleteParams {
+    pub q: String,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
     pub limit: Option<i64>,
 }
 
+impl Validate for AutocompleteParams {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        validation::optional_location(self.lat, self.lon)?;
+        validation::limit(self.limit, config)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateUserBusinessRequest {
     pub id: Uuid,
@@ -43,6 +487,27 @@ pub struct CreateUserBusinessRequest {
     pub logo_map_url: Option<String>,
     pub average_reviews: Option<BigDecimal>,
     pub review_count: Option<i32>, 
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub website: Option<String>,
+    pub opening_hours: Option<String>,
+    /// Existing business (usually imported from OSM) that this registration claims.
+    /// The registered row takes over its OSM identity and the original row is removed.
+    pub claim_business_id: Option<Uuid>,
+    /// OSM node id claimed by this registration, for businesses not yet imported
+    /// or when the client only knows the OSM id.
+    pub claim_osm_id: Option<i64>,
+}
+
+fn ranking_weights(state: &AppState) -> RankingWeights {
+    RankingWeights {
+        distance: state.config.ranking_distance_weight,
+        rating: state.config.ranking_rating_weight,
+        registered: state.config.ranking_registered_weight,
+        distance_decay_meters: state.config.ranking_distance_decay_meters,
+        rating_prior_mean: state.config.ranking_rating_prior_mean,
+        rating_prior_reviews: state.config.ranking_rating_prior_reviews,
+    }
 }
 
 pub async fn sync_businesses(
@@ -51,7 +516,7 @@ pub async fn sync_businesses(
 ) -> Result<Json<SyncResponse>, StatusCode> {
     let country_code = params.country_code.unwrap_or_else(|| "BG".to_string());
     
-    let synced_count = state.business_service
+    let stats = state.business_service
         .sync_businesses(&country_code)
         .await
         .map_err(|e| {
@@ -60,48 +525,373 @@ pub async fn sync_businesses(
         })?;
 
     Ok(Json(SyncResponse {
-        businesses_synced: synced_count,
-        message: format!("Successfully synced {} businesses", synced_count),
+        businesses_synced: stats.synced,
+        message: format!("Successfully synced {} businesses", stats.synced),
+        statistics: stats,
     }))
 }
 
 pub async fn sync_user_business(
     State(state): State<Arc<AppState>>,
     Json(req): Json<CreateUserBusinessRequest>,
-) -> Result<Json<BusinessResponse>, StatusCode> {
+) -> Result<Json<BusinessResponse>, AppError> {
     let business = state.business_service
         .create_user_business(req)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to create user business: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
     Ok(Json(business.to_response()))
 }
 
+pub async fn get_business(
+    State(state): State<Arc<AppState>>,
+    ValidatedPath(id): ValidatedPath<Uuid>,
+) -> Result<Json<BusinessDetailsResponse>, AppError> {
+    let business = state.business_service
+        .get_business_details(id)
+        .await?;
+
+    Ok(Json(business.into()))
+}
+
+pub async fn get_business_batch(
+    State(state): State<Arc<AppState>>,
+    ValidatedJson(req): ValidatedJson<BusinessBatchRequest>,
+) -> Result<Json<BusinessBatchResponse>, AppError> {
+    let batch = state.business_service
+        .get_business_batch(&req.ids)
+        .await?;
+
+    Ok(Json(BusinessBatchResponse {
+        businesses: batch.businesses.into_iter().map(Into::into).collect(),
+        missing: batch.missing,
+    }))
+}
+
+/// Facet counts for a search when the client asked for them, run alongside
+/// the search itself
+async fn facets(
+    state: &AppState,
+    requested: bool,
+    area: &SearchArea,
+    filter: &CategoryFilter,
+    attributes: &AttributeFilter,
+) -> Result<Option<SearchFacets>, AppError> {
+    if !requested {
+        return Ok(None);
+    }
+
+    let facets = state.business_service
+        .search_facets(area, filter, attributes)
+        .await?;
+
+    Ok(Some(facets))
+}
+
+/// Runs a radius search with its facets when asked for
+async fn radius_search(
+    state: &AppState,
+    params: &SearchByRadiusAndCategoryQuery,
+) -> Result<(SearchPage<Business>, Option<SearchFacets>), AppError> {
+    // A cursor only makes sense for the sort order it was issued for
+    let cursor = params.cursor
+        .as_deref()
+        .map(|token| {
+            SearchCursor::decode(token)
+                .filter(|cursor| cursor.sort == params.sort)
+                .ok_or_else(|| AppError::validation("cursor", "not a valid cursor for this sort order"))
+        })
+        .transpose()?;
+
+    let query = RadiusSearchQuery {
+        location: (params.latitude, params.longitude),
+        radius_meters: params.radius_meters().unwrap_or_default(),
+        filter: params.category_filter(),
+        attributes: params.attribute_filter(),
+        sort: params.sort,
+        ranking: ranking_weights(state),
+        limit: state.config.search_limit(params.limit, 50),
+        after: cursor,
+    };
+
+    let area = SearchArea::Radius {
+        location: query.location,
+        radius_meters: query.radius_meters,
+    };
+    tokio::try_join!(
+        state.business_service.search_businesses_by_radius_and_categories(&query),
+        facets(state, params.facets, &area, &query.filter, &query.attributes),
+    )
+}
+
+fn radius_search_geojson(page: SearchPage<Business>, facets: Option<SearchFacets>) -> Response {
+    let next_cursor = page.next_cursor.map(|cursor| cursor.encode());
+    geojson(
+        FeatureCollection::from_iter(page.items)
+            .with_member("next_cursor", next_cursor)
+            .with_member("total_estimate", page.total_estimate)
+            .with_facets(facets),
+    )
+}
+
+/// Radius search answering JSON with a plain array of businesses, as it always
+/// has; the paging state travels in the `x-next-cursor` and `x-total-estimate`
+/// headers. The array has nowhere to put facets, so they are only offered
+/// with GeoJSON.
 pub async fn search_businesses_by_radius_and_category(
     State(state): State<Arc<AppState>>,
-    Query(params): Query<SearchByRadiusAndCategoryQuery>,
-) -> Result<Json<Vec<BusinessResponse>>, StatusCode> {
+    format: ResponseFormat,
+    ValidatedQuery(params): ValidatedQuery<SearchByRadiusAndCategoryQuery>,
+) -> Result<Response, AppError> {
+    if params.facets && format == ResponseFormat::Json {
+        return Err(AppError::validation(
+            "facets",
+            "not available in the array response, use /businesses/search/radius",
+        ));
+    }
+
+    let (page, facets) = radius_search(&state, &params).await?;
+
+    let response = match format {
+        ResponseFormat::Json => {
+            let mut headers = HeaderMap::new();
+            if let Some(cursor) = &page.next_cursor {
+                // Hex encoded, so always a valid header value
+                if let Ok(value) = HeaderValue::from_str(&cursor.encode()) {
+                    headers.insert(NEXT_CURSOR_HEADER, value);
+                }
+            }
+            headers.insert(TOTAL_ESTIMATE_HEADER, HeaderValue::from(page.total_estimate));
+
+            let businesses: Vec<BusinessResponse> = page.items.into_iter().map(|b| b.to_response()).collect();
+            (headers, Json(businesses)).into_response()
+        }
+        ResponseFormat::GeoJson => radius_search_geojson(page, facets),
+    };
+
+    Ok(response)
+}
+
+/// Radius search answering JSON with an envelope holding the paging state and facets
+pub async fn search_businesses_by_radius(
+    State(state): State<Arc<AppState>>,
+    format: ResponseFormat,
+    ValidatedQuery(params): ValidatedQuery<SearchByRadiusAndCategoryQuery>,
+) -> Result<Response, AppError> {
+    let (page, facets) = radius_search(&state, &params).await?;
+
+    let response = match format {
+        ResponseFormat::Json => Json(RadiusSearchResponse {
+            businesses: page.items.into_iter().map(|b| b.to_response()).collect(),
+            next_cursor: page.next_cursor.map(|cursor| cursor.encode()),
+            total_estimate: page.total_estimate,
+            facets,
+        })
+        .into_response(),
+        ResponseFormat::GeoJson => radius_search_geojson(page, facets),
+    };
+
+    Ok(response)
+}
+
+pub async fn search_nearest_businesses(
+    State(state): State<Arc<AppState>>,
+    format: ResponseFormat,
+    ValidatedQuery(params): ValidatedQuery<NearestSearchParams>,
+) -> Result<Response, AppError> {
+    let query = NearestSearchQuery {
+        location: (params.latitude, params.longitude),
+        filter: CategoryFilter::new(params.categories.unwrap_or_default(), params.match_mode),
+        max_distance_meters: params.max_distance_km.map(|km| km * 1000.0),
+        limit: state.config.search_limit(params.limit, 10),
+    };
+
     let businesses = state.business_service
-        .search_businesses_by_radius_and_category(
-            params.latitude,
-            params.longitude,
-            params.radius_km,
-            &params.category,
-            params.limit,
-        )
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to search businesses: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .search_nearest_businesses(&query)
+        .await?;
+
+    Ok(format.businesses(businesses))
+}
+
+/// Nearest businesses per category. GeoJSON answers with one flat
+/// FeatureCollection in request order, each feature tagged with its
+/// `category`; a business nearest in several categories appears once per
+/// category.
+pub async fn nearest_businesses_by_category(
+    State(state): State<Arc<AppState>>,
+    format: ResponseFormat,
+    ValidatedQuery(params): ValidatedQuery<NearestByCategoryParams>,
+) -> Result<Response, AppError> {
+    let query = NearestByCategoryQuery {
+        location: (params.lat, params.lon),
+        categories: params.categories(),
+        per_category: params.per_category.unwrap_or(1),
+        max_distance_meters: params.max_distance_km.map(|km| km * 1000.0),
+    };
 
-    let business_responses: Vec<BusinessResponse> = businesses
-        .into_iter()
-        .map(|b| b.to_response())
-        .collect();
+    let groups = state.business_service
+        .search_nearest_businesses_by_category(&query)
+        .await?;
+
+    let response = match format {
+        ResponseFormat::Json => Json(NearestByCategoryResponse {
+            categories: groups
+                .into_iter()
+                .map(|group| CategoryNearestResponse {
+                    category: group.category,
+                    businesses: group.businesses.into_iter().map(|b| b.to_response()).collect(),
+                })
+                .collect(),
+        })
+        .into_response(),
+        ResponseFormat::GeoJson => geojson(FeatureCollection {
+            features: groups
+                .into_iter()
+                .flat_map(|group| {
+                    let category = group.category;
+                    group.businesses.into_iter().map(move |business| {
+                        let feature = Feature::from(business);
+                        Feature {
+                            id: feature.id,
+                            geometry: feature.geometry,
+                            properties: CategoryFeatureProperties {
+                                category: category.clone(),
+                                business: feature.properties,
+                            },
+                        }
+                    })
+                })
+                .collect(),
+            foreign_members: Default::default(),
+        }),
+    };
+
+    Ok(response)
+}
 
-    Ok(Json(business_responses))
-}
\ No newline at end of file
+pub async fn search_businesses_by_text(
+    State(state): State<Arc<AppState>>,
+    format: ResponseFormat,
+    ValidatedQuery(params): ValidatedQuery<TextSearchParams>,
+) -> Result<Response, AppError> {
+    let query = TextSearchQuery {
+        text: params.q.trim().to_string(),
+        location: params.lat.zip(params.lon),
+        filter: CategoryFilter::new(params.categories.unwrap_or_default(), params.match_mode),
+        limit: state.config.search_limit(params.limit, 20),
+    };
+
+    let businesses = state.business_service
+        .search_businesses_by_text(&query)
+        .await?;
+
+    Ok(format.businesses(businesses))
+}
+
+pub async fn search_businesses_in_bbox(
+    State(state): State<Arc<AppState>>,
+    format: ResponseFormat,
+    ValidatedQuery(params): ValidatedQuery<BboxSearchParams>,
+) -> Result<Response, AppError> {
+    let filter = CategoryFilter::new(params.categories.clone().unwrap_or_default(), params.match_mode);
+    let attributes = params.attribute_filter();
+    let bbox = params.bbox();
+    let area = SearchArea::BoundingBox(bbox);
+    let limit = state.config.search_limit(params.limit, MAX_BBOX_RESULTS);
+
+    let (results, facets) = tokio::try_join!(
+        state.business_service.search_businesses_in_bbox(&bbox, &filter, &attributes, limit),
+        facets(&state, params.facets, &area, &filter, &attributes),
+    )?;
+
+    let response = match format {
+        ResponseFormat::Json => Json(BboxSearchResponse {
+            businesses: results.items.into_iter().map(|b| b.to_response()).collect(),
+            truncated: results.truncated,
+            facets,
+        })
+        .into_response(),
+        ResponseFormat::GeoJson => geojson(
+            FeatureCollection::from_iter(results.items)
+                .with_member("truncated", results.truncated)
+                .with_facets(facets),
+        ),
+    };
+
+    Ok(response)
+}
+
+pub async fn search_businesses_along_route(
+    State(state): State<Arc<AppState>>,
+    format: ResponseFormat,
+    ValidatedJson(req): ValidatedJson<RouteSearchRequest>,
+) -> Result<Response, AppError> {
+    let query = RouteSearchQuery {
+        route: req.route()?,
+        corridor_meters: req.corridor_m.unwrap_or(1000.0),
+        filter: CategoryFilter::new(req.categories, req.match_mode),
+        limit: state.config.search_limit(req.limit, 100),
+    };
+
+    let businesses = state.business_service
+        .search_businesses_along_route(&query)
+        .await?;
+
+    Ok(format.businesses(businesses))
+}
+
+pub async fn search_businesses_in_area(
+    State(state): State<Arc<AppState>>,
+    format: ResponseFormat,
+    ValidatedJson(req): ValidatedJson<AreaSearchRequest>,
+) -> Result<Response, AppError> {
+    let query = AreaSearchQuery {
+        area: req.geometry,
+        filter: CategoryFilter::new(req.categories, req.match_mode),
+        limit: state.config.search_limit(req.limit, MAX_AREA_RESULTS),
+    };
+
+    let results = state.business_service
+        .search_businesses_in_area(&query)
+        .await?;
+
+    let response = match format {
+        ResponseFormat::Json => Json(AreaSearchResponse {
+            businesses: results.items.into_iter().map(|b| b.to_response()).collect(),
+            truncated: results.truncated,
+        })
+        .into_response(),
+        ResponseFormat::GeoJson => geojson(
+            FeatureCollection::from_iter(results.items).with_member("truncated", results.truncated),
+        ),
+    };
+
+    Ok(response)
+}
+
+pub async fn cluster_businesses(
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(params): ValidatedQuery<ClusterParams>,
+) -> Result<Json<ClusteredBusinessesResponse>, AppError> {
+    let filter = CategoryFilter::new(params.categories.clone().unwrap_or_default(), params.match_mode);
+
+    let clustered = state.business_service
+        .cluster_businesses(&params.bbox(), params.zoom, &filter)
+        .await?;
+
+    Ok(Json(clustered.into()))
+}
+
+pub async fn autocomplete_businesses(
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(params): ValidatedQuery<AutocompleteParams>,
+) -> Result<Json<Vec<AutocompleteSuggestion>>, AppError> {
+    if params.q.trim().chars().count() < 2 {
+        return Ok(Json(Vec::new()));
+    }
+
+    let suggestions = state.business_service
+        .autocomplete(params.q.trim(), params.lat.zip(params.lon), state.config.search_limit(params.limit, 8))
+        .await?;
+
+    Ok(Json(suggestions))
+}
@@ -0,0 +1,100 @@
+use axum::{
+    extract::{Path, State},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use crate::{
+    application::{
+        extractors::{geojson, ResponseFormat, ValidatedQuery},
+        state::AppState,
+        validation::{self, Validate},
+    },
+    config::config::Config,
+    domain::entities::{
+        business::BusinessResponse,
+        category::BusinessCategory,
+        city::CityResponse,
+        geojson::FeatureCollection,
+        search::{comma_separated, CategoryFilter, MatchMode},
+    },
+    domain::services::business_service::MAX_CITY_RESULTS,
+    shared::error::AppError,
+};
+
+/// Deepest page offset of a city listing
+const MAX_CITY_OFFSET: i64 = 10_000;
+
+#[derive(Debug, Deserialize)]
+pub struct CityBusinessesParams {
+    /// Comma-separated categories, e.g. `CarWash,GasStation`
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub categories: Option<Vec<BusinessCategory>>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl Validate for CityBusinessesParams {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        if let Some(offset) = self.offset {
+            validation::in_range("offset", offset, 0, MAX_CITY_OFFSET)?;
+        }
+        validation::limit(self.limit, config)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CityBusinessesResponse {
+    pub city: CityResponse,
+    pub businesses: Vec<BusinessResponse>,
+    /// Offset of the next page; null on the last page
+    pub next_offset: Option<i64>,
+}
+
+pub async fn list_cities(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<Vec<CityResponse>>, AppError> {
+    let cities = state.business_service
+        .list_cities()
+        .await?;
+
+    Ok(Json(cities.into_iter().map(Into::into).collect()))
+}
+
+pub async fn list_businesses_in_city(
+    State(state): State<Arc<AppState>>,
+    Path(slug): Path<String>,
+    format: ResponseFormat,
+    ValidatedQuery(params): ValidatedQuery<CityBusinessesParams>,
+) -> Result<Response, AppError> {
+    let city = state.business_service
+        .get_city(&slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("city {}", slug)))?;
+
+    let filter = CategoryFilter::new(params.categories.unwrap_or_default(), params.match_mode);
+    let offset = params.offset.unwrap_or(0);
+    let results = state.business_service
+        .get_businesses_in_city(&slug, &filter, state.config.search_limit(params.limit, MAX_CITY_RESULTS), Some(offset))
+        .await?;
+
+    let next_offset = results.truncated.then(|| offset + results.items.len() as i64);
+    let response = match format {
+        ResponseFormat::Json => Json(CityBusinessesResponse {
+            city: city.into(),
+            businesses: results.items.into_iter().map(|b| b.to_response()).collect(),
+            next_offset,
+        })
+        .into_response(),
+        ResponseFormat::GeoJson => geojson(
+            FeatureCollection::from_iter(results.items)
+                .with_member("city", serde_json::to_value(CityResponse::from(city)).unwrap_or_default())
+                .with_member("next_offset", next_offset),
+        ),
+    };
+
+    Ok(response)
+}
@@ -0,0 +1,102 @@
+use axum::{
+    extract::State,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use crate::{
+    application::{
+        extractors::{ValidatedPath, ValidatedQuery},
+        state::AppState,
+        validation::{self, Validate},
+    },
+    config::config::Config,
+    domain::entities::{
+        business::BusinessResponse,
+        category::BusinessCategory,
+        data_quality::{DataQualityCriteria, DataQualityIssue, DataQualityReportResponse},
+    },
+    shared::error::AppError,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct DataQualityQuery {
+    /// Overrides the configured number of days after which a business is stale
+    pub stale_days: Option<i32>,
+    /// Page of the category and city groups
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DataQualityIssueQuery {
+    pub stale_days: Option<i32>,
+    pub category: Option<BusinessCategory>,
+    pub city: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Longest stale period the report can be asked for, ten years
+const MAX_STALE_DAYS: i32 = 3650;
+
+impl Validate for DataQualityQuery {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        if let Some(stale_days) = self.stale_days {
+            validation::in_range("stale_days", stale_days, 1, MAX_STALE_DAYS)?;
+        }
+        validation::limit(self.limit, config)?;
+        validation::offset(self.offset)
+    }
+}
+
+impl Validate for DataQualityIssueQuery {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        if let Some(stale_days) = self.stale_days {
+            validation::in_range("stale_days", stale_days, 1, MAX_STALE_DAYS)?;
+        }
+        validation::limit(self.limit, config)?;
+        validation::offset(self.offset)
+    }
+}
+
+fn criteria(state: &AppState, stale_days: Option<i32>) -> DataQualityCriteria {
+    DataQualityCriteria {
+        country_polygon_wkt: state.config.country_polygon_wkt.clone(),
+        stale_after_days: stale_days.unwrap_or(state.config.data_quality_stale_days),
+    }
+}
+
+pub async fn get_data_quality_report(
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(params): ValidatedQuery<DataQualityQuery>,
+) -> Result<Json<DataQualityReportResponse>, AppError> {
+    let report = state.business_service
+        .get_data_quality_report(
+            &criteria(&state, params.stale_days),
+            state.config.search_limit(params.limit, 100),
+            params.offset,
+        )
+        .await?;
+
+    Ok(Json(report.into()))
+}
+
+pub async fn list_businesses_with_issue(
+    State(state): State<Arc<AppState>>,
+    ValidatedPath(issue): ValidatedPath<DataQualityIssue>,
+    ValidatedQuery(params): ValidatedQuery<DataQualityIssueQuery>,
+) -> Result<Json<Vec<BusinessResponse>>, AppError> {
+    let businesses = state.business_service
+        .get_businesses_with_issue(
+            issue,
+            &criteria(&state, params.stale_days),
+            params.category.as_ref(),
+            params.city.as_deref(),
+            state.config.search_limit(params.limit, 50),
+            params.offset,
+        )
+        .await?;
+
+    Ok(Json(businesses.into_iter().map(|b| b.to_response()).collect()))
+}
@@ -0,0 +1,91 @@
+use axum::{
+    extract::State,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use std::sync::Arc;
+use crate::{
+    application::{
+        extractors::{ValidatedJson, ValidatedQuery},
+        state::AppState,
+        validation::{self, Validate},
+    },
+    config::config::Config,
+    domain::entities::{business::BusinessResponse, duplicate::DuplicateCandidateResponse},
+    shared::error::AppError,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicateCandidatesQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl Validate for DuplicateCandidatesQuery {
+    fn validate(&self, config: &Config) -> Result<(), AppError> {
+        validation::limit(self.limit, config)?;
+        validation::offset(self.offset)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DetectDuplicatesResponse {
+    pub candidates_found: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MergeBusinessesRequest {
+    /// Business that survives the merge
+    pub keep_id: Uuid,
+    /// Business that is deleted; its id redirects to `keep_id`
+    pub retire_id: Uuid,
+}
+
+impl Validate for MergeBusinessesRequest {
+    fn validate(&self, _config: &Config) -> Result<(), AppError> {
+        if self.keep_id == self.retire_id {
+            return Err(AppError::validation("retire_id", "must differ from keep_id"));
+        }
+        Ok(())
+    }
+}
+
+pub async fn detect_duplicates(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<DetectDuplicatesResponse>, AppError> {
+    let candidates_found = state.business_service
+        .detect_duplicates(
+            state.config.duplicate_max_distance_meters,
+            state.config.duplicate_min_name_similarity,
+        )
+        .await?;
+
+    Ok(Json(DetectDuplicatesResponse {
+        candidates_found,
+        message: format!("Flagged {} duplicate candidates", candidates_found),
+    }))
+}
+
+pub async fn list_duplicate_candidates(
+    State(state): State<Arc<AppState>>,
+    ValidatedQuery(params): ValidatedQuery<DuplicateCandidatesQuery>,
+) -> Result<Json<Vec<DuplicateCandidateResponse>>, AppError> {
+    let candidates = state.business_service
+        .get_duplicate_candidates(state.config.search_limit(params.limit, 50), params.offset)
+        .await?;
+
+    Ok(Json(candidates.into_iter().map(DuplicateCandidateResponse::from).collect()))
+}
+
+pub async fn merge_businesses(
+    State(state): State<Arc<AppState>>,
+    ValidatedJson(req): ValidatedJson<MergeBusinessesRequest>,
+) -> Result<Json<BusinessResponse>, AppError> {
+    let business = state.business_service
+        .merge_businesses(req.keep_id, req.retire_id)
+        .await?;
+
+    Ok(Json(business.to_response()))
+}
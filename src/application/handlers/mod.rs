@@ -1,2 +1,6 @@
 pub mod business;
-pub mod health;
\ No newline at end of file
+pub mod cities;
+pub mod data_quality;
+pub mod duplicates;
+pub mod health;
+pub mod tiles;
\ No newline at end of file
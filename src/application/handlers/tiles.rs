@@ -0,0 +1,69 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::IntoResponse,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use crate::{
+    application::{extractors::{ValidatedPath, ValidatedQuery}, state::AppState, validation::{self, Validate}},
+    config::config::Config,
+    domain::entities::{
+        category::BusinessCategory,
+        search::{comma_separated, CategoryFilter, MatchMode},
+        tile::{TileCoordinates, MAX_TILE_ZOOM},
+    },
+    shared::error::AppError,
+};
+
+const MVT_CONTENT_TYPE: &str = "application/vnd.mapbox-vector-tile";
+
+/// `y` arrives with the `.mvt` extension attached
+#[derive(Debug, Deserialize)]
+pub struct TilePath {
+    pub z: u32,
+    pub x: u32,
+    pub y: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BusinessTileQuery {
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub categories: Option<Vec<BusinessCategory>>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+}
+
+impl Validate for BusinessTileQuery {
+    fn validate(&self, _config: &Config) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+/// `GET /tiles/businesses/{z}/{x}/{y}.mvt`. The router can't match a static
+/// suffix after a parameter, so `y` arrives with the extension attached.
+pub async fn get_business_tile(
+    State(state): State<Arc<AppState>>,
+    ValidatedPath(TilePath { z, x, y }): ValidatedPath<TilePath>,
+    ValidatedQuery(params): ValidatedQuery<BusinessTileQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    validation::in_range("z", z, 0, MAX_TILE_ZOOM)?;
+    let tile = TileCoordinates::from_path(z, x, &y)
+        .ok_or_else(|| AppError::validation("tile", format!("{}/{}/{} is outside the tile grid", z, x, y)))?;
+
+    let filter = CategoryFilter::new(params.categories.unwrap_or_default(), params.match_mode);
+
+    let body = state.business_service
+        .get_business_tile(&tile, &filter)
+        .await?;
+
+    let cache_control = format!("public, max-age={}", state.config.tile_cache_max_age_seconds);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, MVT_CONTENT_TYPE.to_string()),
+            (header::CACHE_CONTROL, cache_control),
+        ],
+        body,
+    ))
+}
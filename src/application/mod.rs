@@ -1,2 +1,4 @@
+pub mod extractors;
 pub mod handlers;
-pub mod state;
\ No newline at end of file
+pub mod state;
+pub mod validation;
\ No newline at end of file
@@ -0,0 +1,126 @@
+use std::fmt::Display;
+
+use crate::{
+    config::config::Config,
+    domain::entities::{
+        area::MAX_AREA_VERTICES,
+        geojson::Geometry,
+        search::BoundingBox,
+    },
+    shared::error::{AppError, Result},
+};
+
+/// Deepest offset list endpoints page to
+const MAX_OFFSET: i64 = 10_000;
+
+/// Checks on deserialized request parameters, run by `ValidatedQuery`
+pub trait Validate {
+    fn validate(&self, config: &Config) -> Result<()>;
+}
+
+pub fn latitude(field: &str, value: f64) -> Result<()> {
+    in_range(field, value, -90.0, 90.0)
+}
+
+pub fn longitude(field: &str, value: f64) -> Result<()> {
+    in_range(field, value, -180.0, 180.0)
+}
+
+/// Optional latitude and longitude that must come together
+pub fn optional_location(lat: Option<f64>, lon: Option<f64>) -> Result<()> {
+    match (lat, lon) {
+        (Some(lat), Some(lon)) => {
+            latitude("lat", lat)?;
+            longitude("lon", lon)
+        }
+        (None, None) => Ok(()),
+        (Some(_), None) => Err(AppError::validation("lon", "required together with lat")),
+        (None, Some(_)) => Err(AppError::validation("lat", "required together with lon")),
+    }
+}
+
+/// Viewport corners in range and not inverted. Boxes crossing the
+/// antimeridian aren't supported.
+pub fn bounding_box(bbox: &BoundingBox) -> Result<()> {
+    latitude("min_lat", bbox.min_lat)?;
+    latitude("max_lat", bbox.max_lat)?;
+    longitude("min_lon", bbox.min_lon)?;
+    longitude("max_lon", bbox.max_lon)?;
+
+    if bbox.min_lat > bbox.max_lat {
+        return Err(AppError::validation("min_lat", "must not be greater than max_lat"));
+    }
+    if bbox.min_lon > bbox.max_lon {
+        return Err(AppError::validation("min_lon", "must not be greater than max_lon"));
+    }
+    Ok(())
+}
+
+/// Rating and review count thresholds of an attribute filter
+pub fn attributes(min_rating: Option<f64>, min_reviews: Option<i32>) -> Result<()> {
+    if let Some(min_rating) = min_rating {
+        in_range("min_rating", min_rating, 0.0, 5.0)?;
+    }
+    if let Some(min_reviews) = min_reviews {
+        in_range("min_reviews", min_reviews, 0, i32::MAX)?;
+    }
+    Ok(())
+}
+
+/// A Polygon or MultiPolygon with closed rings of in-range positions and at
+/// most `MAX_AREA_VERTICES` vertices
+pub fn polygon(field: &str, geometry: &Geometry) -> Result<()> {
+    let rings = geometry
+        .polygon_rings()
+        .ok_or_else(|| AppError::validation(field, "must be a Polygon or MultiPolygon"))?;
+    if rings.is_empty() {
+        return Err(AppError::validation(field, "has no rings"));
+    }
+
+    let vertices: usize = rings.iter().map(|ring| ring.len()).sum();
+    if vertices > MAX_AREA_VERTICES {
+        return Err(AppError::validation(
+            field,
+            format!("has {} vertices, at most {} are allowed", vertices, MAX_AREA_VERTICES),
+        ));
+    }
+
+    for ring in rings {
+        if ring.len() < 4 || ring.first() != ring.last() {
+            return Err(AppError::validation(field, "rings must be closed with at least 4 positions"));
+        }
+        for [lon, lat] in ring {
+            longitude(field, *lon)?;
+            latitude(field, *lat)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn in_range<T: PartialOrd + Display>(field: &str, value: T, min: T, max: T) -> Result<()> {
+    // Written so NaN fails too
+    if value >= min && value <= max {
+        Ok(())
+    } else {
+        Err(AppError::validation(
+            field,
+            format!("must be between {} and {}, got {}", min, max, value),
+        ))
+    }
+}
+
+/// Page size within the configured maximum
+pub fn limit(value: Option<i64>, config: &Config) -> Result<()> {
+    match value {
+        Some(limit) => in_range("limit", limit, 1, config.max_search_limit),
+        None => Ok(()),
+    }
+}
+
+/// Page offset from 0 to `MAX_OFFSET`
+pub fn offset(value: Option<i64>) -> Result<()> {
+    match value {
+        Some(offset) => in_range("offset", offset, 0, MAX_OFFSET),
+        None => Ok(()),
+    }
+}
@@ -7,9 +7,37 @@ pub struct Config {
     pub server_port: u16,
     pub overpass_api_url: String,
     pub overpass_timeout: u64,
+    pub duplicate_max_distance_meters: f64,
+    pub duplicate_min_name_similarity: f32,
+    /// WKT polygon (EPSG:4326) that imported coordinates are expected to fall into
+    pub country_polygon_wkt: String,
+    pub data_quality_stale_days: i32,
+    /// How long clients and CDNs may cache vector tiles
+    pub tile_cache_max_age_seconds: u32,
+    /// Weights of proximity, rating and registration in the best match score
+    pub ranking_distance_weight: f64,
+    pub ranking_rating_weight: f64,
+    pub ranking_registered_weight: f64,
+    pub ranking_distance_decay_meters: f64,
+    /// Bayesian prior ratings are pulled towards, and how many reviews it is worth
+    pub ranking_rating_prior_mean: f64,
+    pub ranking_rating_prior_reviews: f64,
+    /// Largest radius and page size search endpoints accept
+    pub max_search_radius_km: f64,
+    pub max_search_limit: i64,
 }
 
+/// Outline of Bulgaria, traced along the Danube and Black Sea banks so riverside
+/// and coastal towns fall inside. Queries widen it by a small margin.
+const DEFAULT_COUNTRY_POLYGON_WKT: &str = "POLYGON((22.68 44.22, 22.80 44.17, 22.91 44.00, 22.96 43.85, 23.25 43.84, 23.52 43.83, 23.73 43.80, 23.96 43.75, 24.35 43.72, 24.77 43.71, 24.90 43.72, 25.35 43.63, 25.60 43.66, 25.85 43.76, 25.96 43.88, 26.10 43.94, 26.28 43.99, 26.62 44.06, 26.90 44.13, 27.27 44.13, 27.69 43.97, 27.97 43.82, 28.58 43.74, 28.63 43.55, 28.50 43.37, 28.18 43.40, 27.98 43.20, 27.92 42.95, 27.91 42.70, 27.76 42.66, 27.66 42.56, 27.52 42.50, 27.72 42.43, 27.78 42.27, 27.87 42.17, 27.96 42.10, 28.03 41.98, 27.56 41.93, 27.27 42.09, 26.96 42.00, 26.57 41.92, 26.36 41.72, 26.22 41.73, 26.17 41.62, 26.19 41.50, 26.12 41.36, 25.92 41.31, 25.40 41.27, 25.10 41.34, 24.80 41.39, 24.60 41.43, 24.33 41.52, 24.05 41.53, 23.90 41.45, 23.63 41.38, 23.35 41.37, 23.10 41.33, 22.93 41.34, 22.97 41.63, 22.87 41.90, 22.87 42.00, 22.48 42.23, 22.36 42.32, 22.54 42.46, 22.44 42.58, 22.60 42.90, 22.86 42.99, 22.99 43.21, 22.50 43.64, 22.41 44.01, 22.68 44.22))";
+
 impl Config {
+    /// Page size of a request: `limit` when given (validated against
+    /// `max_search_limit`), otherwise the endpoint's default capped at it
+    pub fn search_limit(&self, limit: Option<i64>, default: i64) -> i64 {
+        limit.unwrap_or_else(|| default.min(self.max_search_limit))
+    }
+
     pub fn from_env() -> Result<Self> {
         dotenvy::dotenv().ok();
 
@@ -21,11 +49,68 @@ impl Config {
             .parse::<u16>()
             .map_err(|_| AppError::Config("Invalid SERVER_PORT".to_string()))?;
 
+        let duplicate_max_distance_meters = env::var("DUPLICATE_MAX_DISTANCE_METERS")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse::<f64>()
+            .map_err(|_| AppError::Config("Invalid DUPLICATE_MAX_DISTANCE_METERS".to_string()))?;
+
+        let duplicate_min_name_similarity = env::var("DUPLICATE_MIN_NAME_SIMILARITY")
+            .unwrap_or_else(|_| "0.4".to_string())
+            .parse::<f32>()
+            .map_err(|_| AppError::Config("Invalid DUPLICATE_MIN_NAME_SIMILARITY".to_string()))?;
+
+        let country_polygon_wkt = env::var("COUNTRY_POLYGON_WKT")
+            .unwrap_or_else(|_| DEFAULT_COUNTRY_POLYGON_WKT.to_string());
+
+        let data_quality_stale_days = env::var("DATA_QUALITY_STALE_DAYS")
+            .unwrap_or_else(|_| "180".to_string())
+            .parse::<i32>()
+            .map_err(|_| AppError::Config("Invalid DATA_QUALITY_STALE_DAYS".to_string()))?;
+
+        let tile_cache_max_age_seconds = env::var("TILE_CACHE_MAX_AGE_SECONDS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse::<u32>()
+            .map_err(|_| AppError::Config("Invalid TILE_CACHE_MAX_AGE_SECONDS".to_string()))?;
+
+        let ranking_distance_weight = env_f64("RANKING_DISTANCE_WEIGHT", "0.5")?;
+        let ranking_rating_weight = env_f64("RANKING_RATING_WEIGHT", "0.3")?;
+        let ranking_registered_weight = env_f64("RANKING_REGISTERED_WEIGHT", "0.2")?;
+        let ranking_distance_decay_meters = env_f64("RANKING_DISTANCE_DECAY_METERS", "3000")?;
+        let ranking_rating_prior_mean = env_f64("RANKING_RATING_PRIOR_MEAN", "3.5")?;
+        let ranking_rating_prior_reviews = env_f64("RANKING_RATING_PRIOR_REVIEWS", "5")?;
+
+        let max_search_radius_km = env_f64("MAX_SEARCH_RADIUS_KM", "50")?;
+
+        let max_search_limit = env::var("MAX_SEARCH_LIMIT")
+            .unwrap_or_else(|_| "200".to_string())
+            .parse::<i64>()
+            .map_err(|_| AppError::Config("Invalid MAX_SEARCH_LIMIT".to_string()))?;
+
         Ok(Config {
             database_url,
             server_port,
             overpass_api_url: "https://overpass-api.de/api/interpreter".to_string(),
             overpass_timeout: 50,
+            duplicate_max_distance_meters,
+            duplicate_min_name_similarity,
+            country_polygon_wkt,
+            data_quality_stale_days,
+            tile_cache_max_age_seconds,
+            ranking_distance_weight,
+            ranking_rating_weight,
+            ranking_registered_weight,
+            ranking_distance_decay_meters,
+            ranking_rating_prior_mean,
+            ranking_rating_prior_reviews,
+            max_search_radius_km,
+            max_search_limit,
         })
     }
+}
+
+fn env_f64(name: &str, default: &str) -> Result<f64> {
+    env::var(name)
+        .unwrap_or_else(|_| default.to_string())
+        .parse::<f64>()
+        .map_err(|_| AppError::Config(format!("Invalid {}", name)))
 }
\ No newline at end of file
@@ -0,0 +1,16 @@
+use crate::domain::entities::geojson::Geometry;
+use crate::domain::entities::search::CategoryFilter;
+
+/// Most vertices accepted across all rings of a search area
+pub const MAX_AREA_VERTICES: usize = 5_000;
+
+/// Largest search area, about the size of a Bulgarian province
+pub const MAX_AREA_SQ_KM: f64 = 10_000.0;
+
+/// Businesses inside a Polygon or MultiPolygon
+#[derive(Debug, Clone)]
+pub struct AreaSearchQuery {
+    pub area: Geometry,
+    pub filter: CategoryFilter,
+    pub limit: i64,
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use uuid::Uuid;
+
+use crate::domain::entities::category::{BusinessCategory, Language};
+
+/// A typeahead suggestion for the search bar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum AutocompleteSuggestion {
+    Category {
+        category: BusinessCategory,
+        text: String,
+    },
+    City {
+        text: String,
+        business_count: i64,
+    },
+    Business {
+        id: Uuid,
+        text: String,
+        city: Option<String>,
+    },
+    Specialization {
+        text: String,
+        business_count: i64,
+    },
+}
+
+impl AutocompleteSuggestion {
+    /// Categories with a word of their English or Bulgarian name starting with
+    /// `text`, labelled in the language the user is typing in
+    pub fn matching_categories(text: &str) -> Vec<Self> {
+        let prefix = text.trim().to_lowercase();
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+        let language = Language::detect(text);
+
+        BusinessCategory::iter()
+            .filter(|category| {
+                [Language::En, Language::Bg].iter().any(|lang| {
+                    category.localized_name(*lang)
+                        .to_lowercase()
+                        .split_whitespace()
+                        .any(|word| word.starts_with(&prefix))
+                })
+            })
+            .map(|category| Self::Category {
+                text: category.localized_name(language).to_string(),
+                category,
+            })
+            .collect()
+    }
+}
@@ -5,7 +5,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::prelude::FromRow;
 use uuid::Uuid;
 
-use crate::domain::entities::category::BusinessCategory;
+use crate::domain::entities::category::{BusinessCategory, CategorySource};
+use crate::domain::entities::route::RoutePosition;
+use crate::domain::entities::search::Proximity;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Business {
@@ -25,6 +27,38 @@ pub struct Business {
     pub city: Option<String>,
     pub average_reviews: f64,
     pub review_count: i32, 
+    /// Distance and bearing from the search point, for location-based searches
+    #[sqlx(skip)]
+    pub proximity: Option<Proximity>,
+    /// Position relative to the route, for route searches
+    #[sqlx(skip)]
+    pub route_position: Option<RoutePosition>,
+}
+
+/// Most ids accepted by a batch lookup
+pub const MAX_BUSINESS_BATCH: usize = 100;
+
+/// A business with the details that searches leave out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BusinessDetails {
+    pub business: Business,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub website: Option<String>,
+    /// OSM `opening_hours` value, e.g. `Mo-Fr 08:00-18:00`
+    pub opening_hours: Option<String>,
+    /// Categories inferred from the name only, without a supporting tag
+    pub inferred_categories: Vec<BusinessCategory>,
+}
+
+/// Result of a batch lookup
+#[derive(Debug, Clone, Default)]
+pub struct BusinessBatch {
+    /// Found businesses in request order; ids redirected to the same business
+    /// give it once
+    pub businesses: Vec<BusinessDetails>,
+    /// Requested ids with no business
+    pub missing: Vec<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,7 +70,13 @@ pub struct BusinessInsert {
     pub latitude: f64,
     pub longitude: f64,
     pub categories: Vec<BusinessCategory>,
+    /// Subset of `categories` inferred from the name only, without a supporting tag
+    pub inferred_categories: Vec<BusinessCategory>,
     pub city: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub website: Option<String>,
+    pub opening_hours: Option<String>,
 }
 
 #[allow(non_snake_case)]
@@ -50,6 +90,42 @@ pub struct BusinessResponse {
     pub media: Media,
     pub isRegistered: bool,
     pub rating: Rating,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distanceMeters: Option<f64>,
+    /// Rounded for display, e.g. "1.2 km"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distanceText: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bearingDegrees: Option<f64>,
+    /// Eight-point compass direction from the search point, e.g. "NE"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bearingCompass: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distanceFromRouteMeters: Option<f64>,
+    /// Distance along the route from its start to the business
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alongRouteMeters: Option<f64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BusinessDetailsResponse {
+    #[serde(flatten)]
+    pub business: BusinessResponse,
+    pub nameEn: Option<String>,
+    pub osmId: Option<i64>,
+    pub contact: Contact,
+    pub openingHours: Option<String>,
+    pub inferredCategories: Vec<String>,
+    pub createdAt: DateTime<Utc>,
+    pub updatedAt: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Contact {
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub website: Option<String>,
 }
 
 #[allow(non_snake_case)]
@@ -80,7 +156,13 @@ impl BusinessInsert {
         lon: f64,
         tags: HashMap<String, String>,
     ) -> Self {
-        let categories = BusinessCategory::from_osm_tags(&tags);
+        let matches = BusinessCategory::classify_osm_tags(&tags);
+        let categories = matches.iter().map(|m| m.category.clone()).collect();
+        let inferred_categories = matches
+            .into_iter()
+            .filter(|m| m.source == CategorySource::NameKeyword)
+            .map(|m| m.category)
+            .collect();
 
         Self {
             osm_id,
@@ -90,10 +172,22 @@ impl BusinessInsert {
             latitude: lat,
             longitude: lon,
             categories,
+            inferred_categories,
             city: tags.get("addr:city").or_else(|| tags.get("city")).cloned(),
+            phone: Self::contact_tag(&tags, "phone"),
+            email: Self::contact_tag(&tags, "email"),
+            website: Self::contact_tag(&tags, "website"),
+            opening_hours: tags.get("opening_hours").cloned(),
         }
     }
 
+    /// Contact details are tagged either as `phone` or `contact:phone`
+    fn contact_tag(tags: &HashMap<String, String>, key: &str) -> Option<String> {
+        tags.get(key)
+            .or_else(|| tags.get(&format!("contact:{}", key)))
+            .cloned()
+    }
+
     fn build_address(tags: &HashMap<String, String>) -> Option<String> {
         let mut parts = Vec::new();
         if let Some(street) = tags.get("addr:street") {
@@ -151,6 +245,35 @@ impl From<Business> for BusinessResponse {
                 numReviews: business.review_count 
             },
             isRegistered: business.is_registered,
+            distanceMeters: business.proximity.map(|p| p.distance_meters),
+            distanceText: business.proximity.map(|p| p.display_distance()),
+            bearingDegrees: business.proximity.map(|p| p.bearing_degrees),
+            bearingCompass: business.proximity.map(|p| p.compass_point().to_string()),
+            distanceFromRouteMeters: business.route_position.map(|r| r.distance_from_route_meters),
+            alongRouteMeters: business.route_position.map(|r| r.along_route_meters),
+        }
+    }
+}
+
+impl From<BusinessDetails> for BusinessDetailsResponse {
+    fn from(details: BusinessDetails) -> Self {
+        let business = details.business;
+        Self {
+            nameEn: business.name_en.clone(),
+            osmId: business.osm_id,
+            contact: Contact {
+                phone: details.phone,
+                email: details.email,
+                website: details.website,
+            },
+            openingHours: details.opening_hours,
+            inferredCategories: details.inferred_categories
+                .iter()
+                .map(|c| c.display_name().to_string())
+                .collect(),
+            createdAt: business.created_at,
+            updatedAt: business.updated_at,
+            business: business.into(),
         }
     }
 }
\ No newline at end of file
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use serde::{Deserialize, Serialize};
-use strum_macros::Display;
+use strum_macros::{Display, EnumIter, EnumString};
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type, Display)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type, Display, EnumString, EnumIter)]
 #[sqlx(type_name = "search._business_category", rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
 pub enum BusinessCategory {
@@ -19,126 +19,419 @@ pub enum BusinessCategory {
     Tuning,
     TireShop,
     CarInspectionStation,
+    MotorcycleShop,
+    TruckRepair,
+    Towing,
+    AutoParts,
+    AutoGlass,
+    CarAudio,
+    DrivingSchool,
 }
 
+/// Order in which a business's categories are considered for its primary one,
+/// e.g. the symbol drawn on the map. Establishments that usually bundle other
+/// services (a fuel station with a car wash, a dealer with a workshop) come
+/// first so their main trade wins; parking, attached to many businesses, last.
+pub const PRIMARY_CATEGORY_ORDER: &[BusinessCategory] = &[
+    BusinessCategory::GasStation,
+    BusinessCategory::ElectricVehicleChargingStation,
+    BusinessCategory::CarDealer,
+    BusinessCategory::CarRental,
+    BusinessCategory::CarInspectionStation,
+    BusinessCategory::DrivingSchool,
+    BusinessCategory::Towing,
+    BusinessCategory::TruckRepair,
+    BusinessCategory::MotorcycleShop,
+    BusinessCategory::TireShop,
+    BusinessCategory::RimsShop,
+    BusinessCategory::AutoGlass,
+    BusinessCategory::CarAudio,
+    BusinessCategory::Tuning,
+    BusinessCategory::DetailingStudio,
+    BusinessCategory::CarWash,
+    BusinessCategory::AutoParts,
+    BusinessCategory::CarRepair,
+    BusinessCategory::Mobile,
+    BusinessCategory::Parking,
+];
+
+/// Where a category assigned to an OSM element came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CategorySource {
+    /// An OSM tag explicitly describes the category
+    Tag,
+    /// Inferred from keywords in the business name only
+    NameKeyword,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryMatch {
+    pub category: BusinessCategory,
+    pub source: CategorySource,
+    /// 1.0 for tag matches, lower for name-only inference
+    pub confidence: f32,
+}
+
+/// Value matching any tag value except "no"
+pub const ANY_TAG_VALUE: &str = "*";
+
+/// OSM tags and the category they describe. The Overpass query is generated
+/// from this table, so every tag the classifier understands is also fetched.
+/// `Mobile` has no entry: OSM tags places, and there is no established tag for
+/// a service that comes to the customer, so it is only inferred from names.
+pub const OSM_TAG_MAPPINGS: &[(&str, &str, BusinessCategory)] = &[
+    ("amenity", "fuel", BusinessCategory::GasStation),
+    ("amenity", "charging_station", BusinessCategory::ElectricVehicleChargingStation),
+    ("amenity", "car_wash", BusinessCategory::CarWash),
+    ("amenity", "car_rental", BusinessCategory::CarRental),
+    ("amenity", "parking", BusinessCategory::Parking),
+    ("amenity", "parking_space", BusinessCategory::Parking),
+    ("amenity", "vehicle_inspection", BusinessCategory::CarInspectionStation),
+    ("shop", "car_repair", BusinessCategory::CarRepair),
+    ("shop", "car_parts", BusinessCategory::AutoParts),
+    ("shop", "car", BusinessCategory::CarDealer),
+    ("shop", "tyres", BusinessCategory::TireShop),
+    ("shop", "wheels", BusinessCategory::RimsShop),
+    ("craft", "car_repair", BusinessCategory::CarRepair),
+    ("craft", "automotive", BusinessCategory::CarRepair),
+    ("service", "vehicle_inspection", BusinessCategory::CarInspectionStation),
+    ("service", "car_wash", BusinessCategory::CarWash),
+    ("automotive", "car_wash", BusinessCategory::CarWash),
+    ("automotive", "car_repair", BusinessCategory::CarRepair),
+    ("automotive", "fuel", BusinessCategory::GasStation),
+    ("service:vehicle:car_repair", "yes", BusinessCategory::CarRepair),
+    ("service:vehicle:detailing", "yes", BusinessCategory::DetailingStudio),
+    ("service:vehicle:tuning", "yes", BusinessCategory::Tuning),
+    ("service:vehicle:tyres", "yes", BusinessCategory::TireShop),
+    ("service:vehicle:inspection", "yes", BusinessCategory::CarInspectionStation),
+    ("service:vehicle:car_wash", "yes", BusinessCategory::CarWash),
+    ("car_wash", ANY_TAG_VALUE, BusinessCategory::CarWash),
+    ("shop", "motorcycle", BusinessCategory::MotorcycleShop),
+    ("shop", "motorcycle_repair", BusinessCategory::MotorcycleShop),
+    ("craft", "motorcycle_repair", BusinessCategory::MotorcycleShop),
+    ("shop", "truck_repair", BusinessCategory::TruckRepair),
+    ("service:vehicle:truck_repair", "yes", BusinessCategory::TruckRepair),
+    ("service", "towing", BusinessCategory::Towing),
+    ("service:vehicle:towing", "yes", BusinessCategory::Towing),
+    ("emergency", "roadside_assistance", BusinessCategory::Towing),
+    ("shop", "car_glass", BusinessCategory::AutoGlass),
+    ("service:vehicle:glass", "yes", BusinessCategory::AutoGlass),
+    ("shop", "car_audio", BusinessCategory::CarAudio),
+    ("service:vehicle:car_audio", "yes", BusinessCategory::CarAudio),
+    ("amenity", "driving_school", BusinessCategory::DrivingSchool),
+];
+
+/// Name keywords per category with their confidence. Cyrillic single words
+/// match as a prefix of any word in the name (so stems cover Bulgarian
+/// inflections), Latin ones (English words and brands) only as a whole word,
+/// and phrases match at the start of a word anywhere in the normalized name.
+/// Ambiguous words ("мивка" is also a kitchen sink, "гуми" also rubber goods,
+/// "moto" also starts "motors") stay below `MIN_INFERENCE_CONFIDENCE` and only
+/// count next to another keyword of the same category.
+const NAME_KEYWORDS: &[(BusinessCategory, &str, f32)] = &[
+    (BusinessCategory::CarWash, "автомивк", 0.9),
+    (BusinessCategory::CarWash, "авто мивк", 0.9),
+    (BusinessCategory::CarWash, "мивк", 0.4),
+    (BusinessCategory::CarWash, "car wash", 0.9),
+    (BusinessCategory::CarWash, "carwash", 0.9),
+    (BusinessCategory::CarWash, "wash", 0.4),
+    (BusinessCategory::Mobile, "мобилен сервиз", 0.8),
+    (BusinessCategory::Mobile, "мобилна вулканизация", 0.8),
+    (BusinessCategory::Mobile, "на адрес", 0.4),
+    (BusinessCategory::Mobile, "mobile service", 0.8),
+    (BusinessCategory::Mobile, "mobile mechanic", 0.8),
+    (BusinessCategory::Mobile, "мобилн", 0.4),
+    (BusinessCategory::CarRepair, "автосервиз", 0.9),
+    (BusinessCategory::CarRepair, "авто сервиз", 0.9),
+    (BusinessCategory::CarRepair, "автоцентър", 0.7),
+    (BusinessCategory::CarRepair, "автотенекедж", 0.8),
+    (BusinessCategory::CarRepair, "тенекедж", 0.7),
+    (BusinessCategory::CarRepair, "автоелектр", 0.8),
+    (BusinessCategory::CarRepair, "автобояджи", 0.8),
+    (BusinessCategory::CarRepair, "car repair", 0.9),
+    (BusinessCategory::CarRepair, "auto repair", 0.9),
+    (BusinessCategory::CarRepair, "autoservice", 0.9),
+    (BusinessCategory::CarRepair, "auto service", 0.9),
+    (BusinessCategory::CarRepair, "mechanic", 0.7),
+    (BusinessCategory::CarRepair, "сервиз", 0.4),
+    (BusinessCategory::CarRepair, "ремонт", 0.4),
+    (BusinessCategory::CarRepair, "garage", 0.4),
+    (BusinessCategory::Parking, "паркинг", 0.8),
+    (BusinessCategory::Parking, "parking", 0.8),
+    (BusinessCategory::Parking, "гаражи", 0.4),
+    (BusinessCategory::GasStation, "бензиностанц", 0.9),
+    (BusinessCategory::GasStation, "газостанц", 0.9),
+    (BusinessCategory::GasStation, "газстанц", 0.9),
+    (BusinessCategory::GasStation, "gas station", 0.9),
+    (BusinessCategory::GasStation, "petrol station", 0.9),
+    (BusinessCategory::GasStation, "fuel", 0.4),
+    (BusinessCategory::GasStation, "lukoil", 0.8),
+    (BusinessCategory::GasStation, "лукойл", 0.8),
+    (BusinessCategory::GasStation, "shell", 0.4),
+    (BusinessCategory::GasStation, "omv", 0.8),
+    (BusinessCategory::ElectricVehicleChargingStation, "зарядна станц", 0.9),
+    (BusinessCategory::ElectricVehicleChargingStation, "зарядн", 0.4),
+    (BusinessCategory::ElectricVehicleChargingStation, "ev charging", 0.9),
+    (BusinessCategory::ElectricVehicleChargingStation, "charging station", 0.9),
+    (BusinessCategory::ElectricVehicleChargingStation, "charger", 0.4),
+    (BusinessCategory::CarDealer, "автокъщ", 0.9),
+    (BusinessCategory::CarDealer, "авто къщ", 0.9),
+    (BusinessCategory::CarDealer, "автосалон", 0.9),
+    (BusinessCategory::CarDealer, "car dealer", 0.9),
+    (BusinessCategory::CarDealer, "dealership", 0.8),
+    (BusinessCategory::CarDealer, "car sales", 0.8),
+    (BusinessCategory::CarRental, "rent a car", 0.9),
+    (BusinessCategory::CarRental, "rentacar", 0.9),
+    (BusinessCategory::CarRental, "car rental", 0.9),
+    (BusinessCategory::CarRental, "коли под наем", 0.9),
+    (BusinessCategory::CarRental, "автомобили под наем", 0.9),
+    (BusinessCategory::CarRental, "под наем", 0.4),
+    (BusinessCategory::DetailingStudio, "детайлинг", 0.9),
+    (BusinessCategory::DetailingStudio, "detailing", 0.9),
+    (BusinessCategory::DetailingStudio, "керамично покритие", 0.8),
+    (BusinessCategory::DetailingStudio, "полиране", 0.4),
+    (BusinessCategory::DetailingStudio, "polish", 0.4),
+    (BusinessCategory::RimsShop, "джанти", 0.9),
+    (BusinessCategory::RimsShop, "джант", 0.8),
+    (BusinessCategory::RimsShop, "rims", 0.4),
+    (BusinessCategory::RimsShop, "wheels", 0.4),
+    (BusinessCategory::Tuning, "тунинг", 0.9),
+    (BusinessCategory::Tuning, "tuning", 0.9),
+    (BusinessCategory::Tuning, "chiptuning", 0.9),
+    (BusinessCategory::TireShop, "вулканиз", 0.9),
+    (BusinessCategory::TireShop, "автогум", 0.9),
+    (BusinessCategory::TireShop, "гуми", 0.4),
+    (BusinessCategory::TireShop, "гумаджи", 0.9),
+    (BusinessCategory::TireShop, "tyres", 0.8),
+    (BusinessCategory::TireShop, "tires", 0.8),
+    (BusinessCategory::TireShop, "tyre", 0.8),
+    (BusinessCategory::TireShop, "tire", 0.4),
+    (BusinessCategory::CarInspectionStation, "гтп", 0.9),
+    (BusinessCategory::CarInspectionStation, "технически преглед", 0.9),
+    (BusinessCategory::CarInspectionStation, "технически прегледи", 0.9),
+    (BusinessCategory::CarInspectionStation, "vehicle inspection", 0.9),
+    (BusinessCategory::CarInspectionStation, "прегледи", 0.4),
+    (BusinessCategory::MotorcycleShop, "мотосервиз", 0.9),
+    (BusinessCategory::MotorcycleShop, "мотоцикл", 0.8),
+    (BusinessCategory::MotorcycleShop, "motorcycle", 0.8),
+    (BusinessCategory::MotorcycleShop, "moto", 0.4),
+    (BusinessCategory::MotorcycleShop, "мото", 0.4),
+    (BusinessCategory::TruckRepair, "камионен сервиз", 0.9),
+    (BusinessCategory::TruckRepair, "камион", 0.4),
+    (BusinessCategory::TruckRepair, "тир сервиз", 0.9),
+    (BusinessCategory::TruckRepair, "truck service", 0.9),
+    (BusinessCategory::TruckRepair, "truck repair", 0.9),
+    (BusinessCategory::TruckRepair, "truck", 0.4),
+    (BusinessCategory::Towing, "пътна помощ", 0.9),
+    (BusinessCategory::Towing, "репатрак", 0.9),
+    (BusinessCategory::Towing, "towing", 0.9),
+    (BusinessCategory::Towing, "roadside assistance", 0.9),
+    (BusinessCategory::Towing, "road assistance", 0.9),
+    (BusinessCategory::AutoParts, "авточаст", 0.9),
+    (BusinessCategory::AutoParts, "авто част", 0.9),
+    (BusinessCategory::AutoParts, "резервни части", 0.8),
+    (BusinessCategory::AutoParts, "auto parts", 0.9),
+    (BusinessCategory::AutoParts, "car parts", 0.9),
+    (BusinessCategory::AutoParts, "autoparts", 0.9),
+    (BusinessCategory::AutoGlass, "автостъкл", 0.9),
+    (BusinessCategory::AutoGlass, "авто стъкл", 0.9),
+    (BusinessCategory::AutoGlass, "auto glass", 0.9),
+    (BusinessCategory::AutoGlass, "autoglass", 0.9),
+    (BusinessCategory::AutoGlass, "стъкла", 0.4),
+    (BusinessCategory::CarAudio, "car audio", 0.9),
+    (BusinessCategory::CarAudio, "кар аудио", 0.9),
+    (BusinessCategory::CarAudio, "автоозвучаван", 0.9),
+    (BusinessCategory::CarAudio, "озвучаван", 0.5),
+    (BusinessCategory::CarAudio, "car hifi", 0.8),
+    (BusinessCategory::DrivingSchool, "автошкол", 0.9),
+    (BusinessCategory::DrivingSchool, "авто школ", 0.9),
+    (BusinessCategory::DrivingSchool, "шофьорски курс", 0.9),
+    (BusinessCategory::DrivingSchool, "driving school", 0.9),
+];
+
+/// Keywords used to fetch untagged nodes from Overpass by name. Every node they
+/// match is imported, so only unmistakably automotive words are listed; each
+/// is also a high-confidence entry of `NAME_KEYWORDS`.
+const FETCH_NAME_KEYWORDS: &[&str] = &[
+    "автомивк",
+    "автосервиз",
+    "авто сервиз",
+    "автотенекедж",
+    "автоелектр",
+    "вулканиз",
+    "гумаджи",
+    "автогум",
+    "бензиностанц",
+    "газостанц",
+    "газстанц",
+    "зарядна станц",
+    "автокъщ",
+    "автосалон",
+    "коли под наем",
+    "детайлинг",
+    "технически преглед",
+    "мотосервиз",
+    "тир сервиз",
+    "камионен сервиз",
+    "пътна помощ",
+    "репатрак",
+    "авточаст",
+    "автостъкл",
+    "автошкол",
+    "шофьорски курс",
+    "car wash",
+    "car repair",
+    "auto service",
+    "rent a car",
+    "driving school",
+];
+
+/// Minimum confidence for a name-only match to be assigned
+const MIN_INFERENCE_CONFIDENCE: f32 = 0.5;
+
+/// Extra confidence for every additional keyword of the same category
+const EXTRA_KEYWORD_CONFIDENCE: f32 = 0.1;
+
+/// Name-only inference never reaches the confidence of an explicit tag
+const MAX_INFERENCE_CONFIDENCE: f32 = 0.95;
+
 impl BusinessCategory {
     /// Extract business categories from OSM tags
     pub fn from_osm_tags(tags: &HashMap<String, String>) -> Vec<Self> {
-        let mut categories = Vec::new();
-        
-        // Check amenity tags
-        if let Some(amenity) = tags.get("amenity") {
-            if let Some(category) = Self::from_amenity_tag(amenity) {
-                categories.push(category);
-            }
-        }
-
-        // Check shop tags
-        if let Some(shop) = tags.get("shop") {
-            if let Some(category) = Self::from_shop_tag(shop) {
-                categories.push(category);
-            }
-        }
+        Self::classify_osm_tags(tags)
+            .into_iter()
+            .map(|m| m.category)
+            .collect()
+    }
 
-        // Check craft tags
-        if let Some(craft) = tags.get("craft") {
-            if let Some(category) = Self::from_craft_tag(craft) {
-                categories.push(category);
-            }
-        }
+    /// Classify OSM tags into categories, keeping track of which categories only
+    /// come from the business name and how confident that inference is.
+    pub fn classify_osm_tags(tags: &HashMap<String, String>) -> Vec<CategoryMatch> {
+        let tagged = Self::from_tag_values(tags);
 
-        // Check service tags
-        if let Some(service) = tags.get("service") {
-            if let Some(category) = Self::from_service_tag(service) {
-                categories.push(category);
-            }
-        }
+        let mut matches: Vec<CategoryMatch> = tagged
+            .iter()
+            .map(|category| CategoryMatch {
+                category: category.clone(),
+                source: CategorySource::Tag,
+                confidence: 1.0,
+            })
+            .collect();
 
-        // Check automotive tags
-        if let Some(automotive) = tags.get("automotive") {
-            if let Some(category) = Self::from_automotive_tag(automotive) {
-                categories.push(category);
-            }
+        if let Some(name) = tags.get("name") {
+            matches.extend(
+                Self::infer_from_name(name)
+                    .into_iter()
+                    .filter(|m| !tagged.contains(&m.category)),
+            );
         }
 
-        // Check for car wash by name or specific keys
-        if Self::is_car_wash_by_name_or_key(tags) {
-            categories.push(Self::CarWash);
-        }
+        matches.sort_by(|a, b| a.category.cmp(&b.category));
 
-        // Remove duplicates and sort
-        categories.sort();
-        categories.dedup();
-        
         // Log interesting findings (excluding common ones like parking/gas stations)
+        let categories: Vec<Self> = matches.iter().map(|m| m.category.clone()).collect();
         if !categories.is_empty() && Self::should_log_categories(&categories) {
             tracing::debug!(
                 "Found car-related business with categories: {:?}, key tags: {:?}", 
-                categories, 
+                matches, 
                 Self::extract_key_tags(tags)
             );
         }
-        
-        categories
-    }
 
-    /// Check if OSM element is car-related
-    pub fn is_car_related_osm_element(tags: &HashMap<String, String>) -> bool {
-        !Self::from_osm_tags(tags).is_empty()
+        matches
     }
 
-    // Private helper methods for cleaner organization
-    fn from_amenity_tag(amenity: &str) -> Option<Self> {
-        match amenity {
-            "fuel" => Some(Self::GasStation),
-            "charging_station" => Some(Self::ElectricVehicleChargingStation),
-            "car_wash" => Some(Self::CarWash),
-            "car_rental" => Some(Self::CarRental),
-            "parking" | "parking_space" => Some(Self::Parking),
-            _ => None,
+    /// Infer categories from keywords in a business name, in Bulgarian or English.
+    /// Only matches reaching the minimum confidence are returned.
+    pub fn infer_from_name(name: &str) -> Vec<CategoryMatch> {
+        let words: Vec<String> = name
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect();
+        let normalized = format!(" {} ", words.join(" "));
+
+        let mut scores: Vec<(Self, f32, usize)> = Vec::new();
+        for (category, keyword, confidence) in NAME_KEYWORDS {
+            let found = if keyword.contains(' ') {
+                normalized.contains(&format!(" {}", keyword))
+            } else if keyword.is_ascii() {
+                words.iter().any(|w| w == keyword)
+            } else {
+                words.iter().any(|w| w.starts_with(keyword))
+            };
+            if !found {
+                continue;
+            }
+
+            match scores.iter_mut().find(|(c, _, _)| c == category) {
+                Some((_, best, hits)) => {
+                    *best = best.max(*confidence);
+                    *hits += 1;
+                }
+                None => scores.push((category.clone(), *confidence, 1)),
+            }
         }
+
+        scores
+            .into_iter()
+            .map(|(category, best, hits)| CategoryMatch {
+                category,
+                source: CategorySource::NameKeyword,
+                confidence: (best + EXTRA_KEYWORD_CONFIDENCE * (hits - 1) as f32)
+                    .min(MAX_INFERENCE_CONFIDENCE),
+            })
+            .filter(|m| m.confidence >= MIN_INFERENCE_CONFIDENCE)
+            .collect()
     }
 
-    fn from_shop_tag(shop: &str) -> Option<Self> {
-        match shop {
-            "car_repair" | "car_parts" => Some(Self::CarRepair),
-            "car" => Some(Self::CarDealer),
-            "tyres" => Some(Self::TireShop),
-            "wheels" => Some(Self::RimsShop),
-            _ => None,
-        }
+    /// Categories explicitly described by OSM tags
+    fn from_tag_values(tags: &HashMap<String, String>) -> Vec<Self> {
+        let mut categories: Vec<Self> = OSM_TAG_MAPPINGS
+            .iter()
+            .filter(|(key, value, _)| {
+                tags.get(*key).is_some_and(|tag_value| Self::tag_value_matches(tag_value, value))
+            })
+            .map(|(_, _, category)| category.clone())
+            .collect();
+
+        // Remove duplicates and sort
+        categories.sort();
+        categories.dedup();
+        
+        categories
     }
 
-    fn from_craft_tag(craft: &str) -> Option<Self> {
-        match craft {
-            "car_repair" | "automotive" => Some(Self::CarRepair),
-            _ => None,
+    /// OSM values may hold several `;`-separated entries, e.g. `shop=car;car_repair`
+    fn tag_value_matches(tag_value: &str, expected: &str) -> bool {
+        if expected == ANY_TAG_VALUE {
+            return tag_value != "no";
         }
+        tag_value.split(';').any(|v| v.trim() == expected)
     }
 
-    fn from_service_tag(service: &str) -> Option<Self> {
-        match service {
-            "vehicle_inspection" => Some(Self::CarInspectionStation),
-            "car_wash" => Some(Self::CarWash),
-            _ => None,
+    /// Mapped OSM values grouped by key, used to build the Overpass query
+    pub fn osm_tag_filters() -> BTreeMap<&'static str, Vec<&'static str>> {
+        let mut filters: BTreeMap<&'static str, Vec<&'static str>> = BTreeMap::new();
+        for (key, value, _) in OSM_TAG_MAPPINGS {
+            let values = filters.entry(*key).or_default();
+            if !values.contains(value) {
+                values.push(*value);
+            }
         }
+        filters
     }
 
-    fn from_automotive_tag(automotive: &str) -> Option<Self> {
-        match automotive {
-            "car_wash" => Some(Self::CarWash),
-            "car_repair" => Some(Self::CarRepair),
-            "fuel" => Some(Self::GasStation),
-            _ => None,
-        }
+    /// Curated keywords used to fetch untagged nodes from Overpass by name
+    pub fn name_keywords() -> &'static [&'static str] {
+        FETCH_NAME_KEYWORDS
     }
 
-    fn is_car_wash_by_name_or_key(tags: &HashMap<String, String>) -> bool {
-        tags.contains_key("car_wash") ||
-        tags.get("name").map_or(false, |n| {
-            let name_lower = n.to_lowercase();
-            name_lower.contains("car wash") || name_lower.contains("автомивка")
-        })
+    /// Check if OSM element is car-related
+    pub fn is_car_related_osm_element(tags: &HashMap<String, String>) -> bool {
+        !Self::from_osm_tags(tags).is_empty()
     }
 
     fn should_log_categories(categories: &[Self]) -> bool {
@@ -146,12 +439,13 @@ impl BusinessCategory {
     }
 
     fn extract_key_tags(tags: &HashMap<String, String>) -> HashMap<String, String> {
-        const INTERESTING_KEYS: &[&str] = &[
-            "amenity", "shop", "craft", "service", "automotive", "name", "brand"
-        ];
+        const INTERESTING_KEYS: &[&str] = &["name", "brand"];
         
         tags.iter()
-            .filter(|(k, _)| INTERESTING_KEYS.contains(&k.as_str()))
+            .filter(|(k, _)| {
+                INTERESTING_KEYS.contains(&k.as_str())
+                    || OSM_TAG_MAPPINGS.iter().any(|(key, _, _)| key == k)
+            })
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect()
     }
@@ -172,6 +466,186 @@ impl BusinessCategory {
             Self::Tuning => "Tuning",
             Self::TireShop => "TireShop",
             Self::CarInspectionStation => "CarInspectionStation",
+            Self::MotorcycleShop => "MotorcycleShop",
+            Self::TruckRepair => "TruckRepair",
+            Self::Towing => "Towing",
+            Self::AutoParts => "AutoParts",
+            Self::AutoGlass => "AutoGlass",
+            Self::CarAudio => "CarAudio",
+            Self::DrivingSchool => "DrivingSchool",
+        }
+    }
+
+    /// Human readable category name in the given language
+    pub fn localized_name(&self, language: Language) -> &'static str {
+        match language {
+            Language::En => match self {
+                Self::CarWash => "Car wash",
+                Self::Mobile => "Mobile service",
+                Self::CarRepair => "Car repair",
+                Self::Parking => "Parking",
+                Self::GasStation => "Gas station",
+                Self::ElectricVehicleChargingStation => "EV charging station",
+                Self::CarDealer => "Car dealer",
+                Self::CarRental => "Car rental",
+                Self::DetailingStudio => "Detailing studio",
+                Self::RimsShop => "Rims shop",
+                Self::Tuning => "Tuning",
+                Self::TireShop => "Tire shop",
+                Self::CarInspectionStation => "Vehicle inspection",
+                Self::MotorcycleShop => "Motorcycle shop",
+                Self::TruckRepair => "Truck repair",
+                Self::Towing => "Towing",
+                Self::AutoParts => "Auto parts",
+                Self::AutoGlass => "Auto glass",
+                Self::CarAudio => "Car audio",
+                Self::DrivingSchool => "Driving school",
+            },
+            Language::Bg => match self {
+                Self::CarWash => "Автомивка",
+                Self::Mobile => "Мобилен сервиз",
+                Self::CarRepair => "Автосервиз",
+                Self::Parking => "Паркинг",
+                Self::GasStation => "Бензиностанция",
+                Self::ElectricVehicleChargingStation => "Зарядна станция",
+                Self::CarDealer => "Автокъща",
+                Self::CarRental => "Коли под наем",
+                Self::DetailingStudio => "Детайлинг студио",
+                Self::RimsShop => "Джанти",
+                Self::Tuning => "Тунинг",
+                Self::TireShop => "Вулканизатор",
+                Self::CarInspectionStation => "Технически преглед",
+                Self::MotorcycleShop => "Мотосервиз",
+                Self::TruckRepair => "Сервиз за камиони",
+                Self::Towing => "Пътна помощ",
+                Self::AutoParts => "Авточасти",
+                Self::AutoGlass => "Автостъкла",
+                Self::CarAudio => "Автоозвучаване",
+                Self::DrivingSchool => "Автошкола",
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    En,
+    Bg,
+}
+
+impl Language {
+    /// Bulgarian when the text contains Cyrillic, English otherwise
+    pub fn detect(text: &str) -> Self {
+        if text.chars().any(|c| matches!(c, '\u{0400}'..='\u{04FF}')) {
+            Self::Bg
+        } else {
+            Self::En
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inferred(name: &str) -> Vec<BusinessCategory> {
+        BusinessCategory::infer_from_name(name)
+            .into_iter()
+            .map(|m| m.category)
+            .collect()
+    }
+
+    #[test]
+    fn infers_categories_from_bulgarian_names() {
+        assert_eq!(inferred("Вулканизатор Иван"), vec![BusinessCategory::TireShop]);
+        assert_eq!(inferred("Автомивка Блясък"), vec![BusinessCategory::CarWash]);
+        assert_eq!(inferred("Мотосервиз Петров"), vec![BusinessCategory::MotorcycleShop]);
+        assert_eq!(inferred("ГТП Младост"), vec![BusinessCategory::CarInspectionStation]);
+    }
+
+    #[test]
+    fn infers_categories_from_english_names() {
+        assert_eq!(inferred("Express Car Wash"), vec![BusinessCategory::CarWash]);
+        assert_eq!(inferred("Truck Repair Plovdiv"), vec![BusinessCategory::TruckRepair]);
+        assert_eq!(inferred("Sofia Driving School"), vec![BusinessCategory::DrivingSchool]);
+    }
+
+    #[test]
+    fn name_matches_are_marked_as_inferred() {
+        let matches = BusinessCategory::infer_from_name("Автосервиз Мечо");
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].source, CategorySource::NameKeyword);
+        assert!(matches[0].confidence < 1.0);
+    }
+
+    #[test]
+    fn ambiguous_stems_alone_infer_nothing() {
+        assert!(inferred("Auto Motors").is_empty());
+        assert!(inferred("Моторни масла").is_empty());
+        assert!(inferred("Wheels of Fortune").is_empty());
+        assert!(inferred("Phone Charger Shop").is_empty());
+        assert!(inferred("Truckee Diner").is_empty());
+        assert!(inferred("Polish Deli").is_empty());
+    }
+
+    #[test]
+    fn ambiguous_words_infer_nothing() {
+        assert!(inferred("Мивки и смесители").is_empty());
+        assert!(inferred("Доставка на адрес").is_empty());
+        assert!(inferred("Fuel Fitness").is_empty());
+        assert!(inferred("Sea Shell Bar").is_empty());
+        assert!(inferred("Rims Coffee").is_empty());
+        assert!(inferred("Гуми и ластици").is_empty());
+        assert!(inferred("Tire Kebap").is_empty());
+        assert!(inferred("Полиране на паркет").is_empty());
+        assert!(inferred("Камионски превози").is_empty());
+        assert!(inferred("Зарядни за телефони").is_empty());
+    }
+
+    #[test]
+    fn latin_keywords_match_whole_words_only() {
+        assert!(inferred("Omvtech").is_empty());
+        assert!(inferred("Lukoilino Pizza").is_empty());
+        assert!(inferred("Parkinglot Cafe").is_empty());
+        assert_eq!(inferred("OMV Plovdiv"), vec![BusinessCategory::GasStation]);
+    }
+
+    #[test]
+    fn specific_compounds_are_still_inferred() {
+        assert_eq!(inferred("Гуми и джанти"), vec![BusinessCategory::RimsShop]);
+        assert_eq!(inferred("Автогуми Пеев"), vec![BusinessCategory::TireShop]);
+        assert_eq!(inferred("Камионен сервиз Тракия"), vec![BusinessCategory::TruckRepair]);
+    }
+
+    #[test]
+    fn primary_order_lists_every_category_once() {
+        use strum::IntoEnumIterator;
+
+        for category in BusinessCategory::iter() {
+            let listed = PRIMARY_CATEGORY_ORDER.iter().filter(|c| **c == category).count();
+            assert_eq!(listed, 1, "{:?}", category);
+        }
+    }
+
+    #[test]
+    fn fetch_keywords_are_confident_name_keywords() {
+        for keyword in BusinessCategory::name_keywords() {
+            let confidence = NAME_KEYWORDS
+                .iter()
+                .filter(|(_, k, _)| k == keyword)
+                .map(|(_, _, confidence)| *confidence)
+                .fold(0.0, f32::max);
+            assert!(confidence >= 0.8, "{} is not a confident keyword", keyword);
+        }
+    }
+
+    #[test]
+    fn unrelated_names_infer_nothing() {
+        assert!(inferred("Хлебарница Иван").is_empty());
+        assert!(inferred("Аптека Здраве").is_empty());
+        assert!(inferred("Central Bakery").is_empty());
+        assert!(inferred("").is_empty());
+    }
+}
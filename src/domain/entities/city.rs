@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::category::BusinessCategory;
+
+/// A city with businesses, as listed in the city directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct City {
+    /// Transliterated, hyphenated name used in URLs, e.g. `veliko-tarnovo`
+    pub slug: String,
+    /// Most common spelling among the city's businesses
+    pub name: String,
+    /// Most common Cyrillic spelling, if any business uses one
+    pub name_cyrillic: Option<String>,
+    /// Most common Latin spelling, or the transliterated name
+    pub name_latin: String,
+    /// Centroid of the city's businesses
+    pub latitude: f64,
+    pub longitude: f64,
+    pub business_count: i64,
+    /// Businesses per category; a business with several categories counts in each
+    pub categories: BTreeMap<BusinessCategory, i64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CityResponse {
+    pub slug: String,
+    pub name: String,
+    pub nameCyrillic: Option<String>,
+    pub nameLatin: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub businessCount: i64,
+    pub categories: BTreeMap<String, i64>,
+}
+
+impl From<City> for CityResponse {
+    fn from(city: City) -> Self {
+        Self {
+            slug: city.slug,
+            name: city.name,
+            nameCyrillic: city.name_cyrillic,
+            nameLatin: city.name_latin,
+            latitude: city.latitude,
+            longitude: city.longitude,
+            businessCount: city.business_count,
+            categories: city.categories
+                .into_iter()
+                .map(|(category, count)| (category.display_name().to_string(), count))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::business::{Business, BusinessResponse};
+use crate::domain::entities::category::BusinessCategory;
+use crate::domain::entities::search::BoundingBox;
+
+/// Web map tiles are 256px wide; each tile is split into this many grid cells
+/// per side, so a cluster marker stands for roughly a 64px square on screen
+const GRID_CELLS_PER_TILE: f64 = 4.0;
+
+/// Deepest zoom level clustering is computed for; beyond it the grid cells are
+/// a few meters wide and every business is returned individually anyway
+pub const MAX_CLUSTER_ZOOM: u8 = 20;
+
+/// Most grid cells a clustering request is computed over. Each cell yields one
+/// cluster or fewer than `MIN_CLUSTER_SIZE` businesses, so a response stays at
+/// a few hundred markers; larger viewports get a coarser grid instead.
+pub const MAX_GRID_CELLS: f64 = 256.0;
+
+/// Cells with fewer businesses than this are returned as individual businesses
+pub const MIN_CLUSTER_SIZE: i64 = 3;
+
+/// Width of a clustering grid cell in degrees at a web map zoom level.
+/// The grid is anchored at 0,0 so clusters stay put while the map is panned.
+pub fn grid_cell_degrees(zoom: u8) -> f64 {
+    let zoom = zoom.min(MAX_CLUSTER_ZOOM);
+    360.0 / (2f64.powi(zoom as i32) * GRID_CELLS_PER_TILE)
+}
+
+/// Number of grid cells a viewport spans at a zoom level
+pub fn grid_cell_count(bbox: &BoundingBox, zoom: u8) -> f64 {
+    let cell = grid_cell_degrees(zoom);
+    let columns = ((bbox.max_lon - bbox.min_lon) / cell).ceil().max(1.0);
+    let rows = ((bbox.max_lat - bbox.min_lat) / cell).ceil().max(1.0);
+    columns * rows
+}
+
+/// Zoom level whose grid clusters a viewport: the requested one, or the
+/// deepest coarser level that keeps the viewport within `MAX_GRID_CELLS`.
+/// Coarser levels double the cell width, so their cells still line up with
+/// the finer grid.
+pub fn clustering_zoom(bbox: &BoundingBox, zoom: u8) -> u8 {
+    let mut zoom = zoom.min(MAX_CLUSTER_ZOOM);
+    while zoom > 0 && grid_cell_count(bbox, zoom) > MAX_GRID_CELLS {
+        zoom -= 1;
+    }
+    zoom
+}
+
+/// Businesses grouped into one map marker
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BusinessCluster {
+    /// Centroid of the clustered businesses
+    pub latitude: f64,
+    pub longitude: f64,
+    pub business_count: i64,
+    /// Businesses per category; a business with several categories counts in each
+    pub categories: BTreeMap<BusinessCategory, i64>,
+}
+
+/// Map markers for a viewport: clusters for dense areas, businesses for sparse ones
+#[derive(Debug, Clone, Default)]
+pub struct ClusteredBusinesses {
+    /// Zoom level and cell width of the grid actually used, coarser than
+    /// requested when the viewport is large
+    pub zoom: u8,
+    pub cell_degrees: f64,
+    pub clusters: Vec<BusinessCluster>,
+    pub businesses: Vec<Business>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BusinessClusterResponse {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub businessCount: i64,
+    pub categories: BTreeMap<String, i64>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClusteredBusinessesResponse {
+    pub clusterZoom: u8,
+    pub cellDegrees: f64,
+    pub clusters: Vec<BusinessClusterResponse>,
+    pub businesses: Vec<BusinessResponse>,
+}
+
+impl From<BusinessCluster> for BusinessClusterResponse {
+    fn from(cluster: BusinessCluster) -> Self {
+        Self {
+            latitude: cluster.latitude,
+            longitude: cluster.longitude,
+            businessCount: cluster.business_count,
+            categories: cluster.categories
+                .into_iter()
+                .map(|(category, count)| (category.display_name().to_string(), count))
+                .collect(),
+        }
+    }
+}
+
+impl From<ClusteredBusinesses> for ClusteredBusinessesResponse {
+    fn from(clustered: ClusteredBusinesses) -> Self {
+        Self {
+            clusterZoom: clustered.zoom,
+            cellDegrees: clustered.cell_degrees,
+            clusters: clustered.clusters.into_iter().map(Into::into).collect(),
+            businesses: clustered.businesses.into_iter().map(|b| b.to_response()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bbox(min_lon: f64, min_lat: f64, max_lon: f64, max_lat: f64) -> BoundingBox {
+        BoundingBox { min_lat, min_lon, max_lat, max_lon }
+    }
+
+    #[test]
+    fn small_viewports_keep_the_requested_zoom() {
+        // A few blocks of central Sofia at zoom 14
+        let sofia = bbox(23.30, 42.68, 23.33, 42.70);
+        assert_eq!(clustering_zoom(&sofia, 14), 14);
+    }
+
+    #[test]
+    fn large_viewports_get_a_coarser_grid() {
+        // A 1920x1080px desktop map at zoom 14 spans about 510 cells
+        let desktop = bbox(23.24, 42.65, 23.40, 42.74);
+        assert!(grid_cell_count(&desktop, 14) > MAX_GRID_CELLS);
+
+        let zoom = clustering_zoom(&desktop, 14);
+        assert_eq!(zoom, 13);
+        assert!(grid_cell_count(&desktop, zoom) <= MAX_GRID_CELLS);
+    }
+
+    #[test]
+    fn the_whole_world_fits_at_zoom_zero() {
+        let world = bbox(-180.0, -90.0, 180.0, 90.0);
+        let zoom = clustering_zoom(&world, MAX_CLUSTER_ZOOM);
+        assert!(grid_cell_count(&world, zoom) <= MAX_GRID_CELLS);
+    }
+}
\ No newline at end of file
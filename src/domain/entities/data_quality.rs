@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+
+use crate::domain::entities::category::BusinessCategory;
+
+/// A data quality problem a business can have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Display, EnumIter)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DataQualityIssue {
+    MissingName,
+    MissingAddress,
+    MissingCity,
+    /// No phone, email or website
+    MissingContact,
+    MissingOpeningHours,
+    /// Located outside the configured country polygon
+    OutsideCountry,
+    /// At 0,0 or with an empty geometry
+    ZeroCoordinates,
+    /// Outside the country, but inside it once latitude and longitude are swapped
+    SwappedCoordinates,
+    /// Not updated within the stale period
+    Stale,
+}
+
+/// Parameters the issues are evaluated against
+#[derive(Debug, Clone)]
+pub struct DataQualityCriteria {
+    pub country_polygon_wkt: String,
+    pub stale_after_days: i32,
+}
+
+/// Number of businesses affected by each issue within a group
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DataQualityCounts {
+    pub total: i64,
+    pub issues: BTreeMap<DataQualityIssue, i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataQualityGroup {
+    pub category: BusinessCategory,
+    pub city: Option<String>,
+    pub counts: DataQualityCounts,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataQualityReport {
+    pub stale_after_days: i32,
+    pub summary: DataQualityCounts,
+    /// Number of category and city groups, of which `groups` is one page
+    pub group_count: i64,
+    pub groups: Vec<DataQualityGroup>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueStats {
+    pub count: i64,
+    /// Share of the group's businesses affected, 0.0 - 1.0
+    pub share: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DataQualityCountsResponse {
+    pub total: i64,
+    pub issues: BTreeMap<DataQualityIssue, IssueStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DataQualityGroupResponse {
+    pub category: BusinessCategory,
+    pub city: Option<String>,
+    #[serde(flatten)]
+    pub counts: DataQualityCountsResponse,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DataQualityReportResponse {
+    pub staleAfterDays: i32,
+    pub summary: DataQualityCountsResponse,
+    pub groupCount: i64,
+    pub groups: Vec<DataQualityGroupResponse>,
+}
+
+impl From<DataQualityCounts> for DataQualityCountsResponse {
+    fn from(counts: DataQualityCounts) -> Self {
+        let total = counts.total;
+        Self {
+            total,
+            issues: counts.issues
+                .into_iter()
+                .map(|(issue, count)| {
+                    let share = if total > 0 { count as f64 / total as f64 } else { 0.0 };
+                    (issue, IssueStats { count, share })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<DataQualityReport> for DataQualityReportResponse {
+    fn from(report: DataQualityReport) -> Self {
+        Self {
+            staleAfterDays: report.stale_after_days,
+            summary: report.summary.into(),
+            groupCount: report.group_count,
+            groups: report.groups
+                .into_iter()
+                .map(|group| DataQualityGroupResponse {
+                    category: group.category,
+                    city: group.city,
+                    counts: group.counts.into(),
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::entities::category::BusinessCategory;
+
+/// A pair of businesses flagged as probably being the same place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateCandidate {
+    pub business: DuplicateSide,
+    pub duplicate: DuplicateSide,
+    pub distance_meters: f64,
+    pub name_similarity: f32,
+    pub shared_categories: Vec<BusinessCategory>,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateSide {
+    pub id: Uuid,
+    pub osm_id: Option<i64>,
+    pub name: Option<String>,
+    pub is_registered: bool,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateCandidateResponse {
+    pub business: DuplicateSideResponse,
+    pub duplicate: DuplicateSideResponse,
+    pub distanceMeters: f64,
+    pub nameSimilarity: f32,
+    pub sharedCategories: Vec<String>,
+    pub detectedAt: DateTime<Utc>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateSideResponse {
+    pub id: Uuid,
+    pub osmId: Option<i64>,
+    pub name: Option<String>,
+    pub isRegistered: bool,
+}
+
+impl From<DuplicateSide> for DuplicateSideResponse {
+    fn from(side: DuplicateSide) -> Self {
+        Self {
+            id: side.id,
+            osmId: side.osm_id,
+            name: side.name,
+            isRegistered: side.is_registered,
+        }
+    }
+}
+
+impl From<DuplicateCandidate> for DuplicateCandidateResponse {
+    fn from(candidate: DuplicateCandidate) -> Self {
+        Self {
+            business: candidate.business.into(),
+            duplicate: candidate.duplicate.into(),
+            distanceMeters: candidate.distance_meters,
+            nameSimilarity: candidate.name_similarity,
+            sharedCategories: candidate.shared_categories
+                .iter()
+                .map(|c| c.display_name().to_string())
+                .collect(),
+            detectedAt: candidate.detected_at,
+        }
+    }
+}
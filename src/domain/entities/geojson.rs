@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::domain::entities::business::{Business, BusinessResponse};
+use crate::domain::entities::search::SearchFacets;
+
+pub const GEOJSON_CONTENT_TYPE: &str = "application/geo+json";
+
+/// GeoJSON geometry; positions are `[longitude, latitude]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point { coordinates: [f64; 2] },
+    LineString { coordinates: Vec<[f64; 2]> },
+    /// Outer ring followed by holes; rings are closed
+    Polygon { coordinates: Vec<Vec<[f64; 2]>> },
+    MultiPolygon { coordinates: Vec<Vec<Vec<[f64; 2]>>> },
+}
+
+impl Geometry {
+    /// Rings of a Polygon or MultiPolygon, None for other geometries
+    pub fn polygon_rings(&self) -> Option<Vec<&Vec<[f64; 2]>>> {
+        match self {
+            Geometry::Polygon { coordinates } => Some(coordinates.iter().collect()),
+            Geometry::MultiPolygon { coordinates } => Some(coordinates.iter().flatten().collect()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "Feature")]
+pub struct Feature<P> {
+    pub id: Uuid,
+    pub geometry: Geometry,
+    pub properties: P,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "FeatureCollection")]
+pub struct FeatureCollection<P> {
+    pub features: Vec<Feature<P>>,
+    /// Extra top-level members, e.g. whether the results were truncated
+    #[serde(flatten)]
+    pub foreign_members: Map<String, Value>,
+}
+
+impl<P> FeatureCollection<P> {
+    pub fn with_member(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.foreign_members.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Adds search facet counts as a `facets` member when there are any
+    pub fn with_facets(self, facets: Option<SearchFacets>) -> Self {
+        match facets.and_then(|facets| serde_json::to_value(facets).ok()) {
+            Some(facets) => self.with_member("facets", facets),
+            None => self,
+        }
+    }
+}
+
+impl From<Business> for Feature<BusinessResponse> {
+    fn from(business: Business) -> Self {
+        Self {
+            id: business.id,
+            geometry: Geometry::Point {
+                coordinates: [business.longitude, business.latitude],
+            },
+            properties: business.to_response(),
+        }
+    }
+}
+
+impl FromIterator<Business> for FeatureCollection<BusinessResponse> {
+    fn from_iter<I: IntoIterator<Item = Business>>(businesses: I) -> Self {
+        Self {
+            features: businesses.into_iter().map(Feature::from).collect(),
+            foreign_members: Map::new(),
+        }
+    }
+}
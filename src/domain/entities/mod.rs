@@ -1,2 +1,13 @@
+pub mod area;
+pub mod autocomplete;
 pub mod business;
-pub mod category;
\ No newline at end of file
+pub mod category;
+pub mod city;
+pub mod cluster;
+pub mod data_quality;
+pub mod duplicate;
+pub mod geojson;
+pub mod route;
+pub mod search;
+pub mod sync;
+pub mod tile;
\ No newline at end of file
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::search::CategoryFilter;
+
+/// Most vertices accepted in a route, roughly a cross-country drive
+pub const MAX_ROUTE_POINTS: usize = 10_000;
+
+/// Widest corridor a route search may ask for, on each side of the route
+pub const MAX_ROUTE_CORRIDOR_METERS: f64 = 5_000.0;
+
+/// Meters per degree of latitude, rounded down so derived degree distances err wide
+const METERS_PER_DEGREE: f64 = 110_000.0;
+
+/// Businesses within a corridor around a route
+#[derive(Debug, Clone)]
+pub struct RouteSearchQuery {
+    /// Route vertices as `[longitude, latitude]`, in driving order
+    pub route: Vec<[f64; 2]>,
+    /// Maximum distance from the route
+    pub corridor_meters: f64,
+    pub filter: CategoryFilter,
+    pub limit: i64,
+}
+
+impl RouteSearchQuery {
+    /// The corridor in planar degrees, wide enough at the route's highest
+    /// latitude that an index-assisted prefilter never drops a match
+    pub fn corridor_degrees(&self) -> f64 {
+        let max_latitude = self.route
+            .iter()
+            .map(|[_, lat]| lat.abs())
+            .fold(0.0, f64::max)
+            .min(89.0);
+
+        self.corridor_meters / (METERS_PER_DEGREE * max_latitude.to_radians().cos())
+    }
+}
+
+/// Where a result lies relative to the route
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RoutePosition {
+    pub distance_from_route_meters: f64,
+    /// How far along the route the business is reached
+    pub along_route_meters: f64,
+}
+
+/// Decodes an encoded polyline (Google's algorithm) into `[longitude, latitude]`
+/// points. `precision` is the number of decimal places: 5 for Google, 6 for
+/// OSRM and Valhalla. None if the string is malformed.
+pub fn decode_polyline(encoded: &str, precision: u32) -> Option<Vec<[f64; 2]>> {
+    let factor = 10f64.powi(precision as i32);
+    let mut bytes = encoded.bytes();
+    let mut points = Vec::new();
+    let (mut lat, mut lon) = (0i64, 0i64);
+
+    while bytes.len() > 0 {
+        lat = lat.checked_add(decode_value(&mut bytes)?)?;
+        lon = lon.checked_add(decode_value(&mut bytes)?)?;
+        points.push([lon as f64 / factor, lat as f64 / factor]);
+    }
+
+    Some(points)
+}
+
+fn decode_value(bytes: &mut std::str::Bytes<'_>) -> Option<i64> {
+    let mut result = 0i64;
+    let mut shift = 0;
+
+    loop {
+        let chunk = i64::from(bytes.next()?.checked_sub(63)?);
+        if chunk > 0x3f || shift > 60 {
+            return None;
+        }
+        result |= (chunk & 0x1f) << shift;
+        shift += 5;
+        if chunk < 0x20 {
+            break;
+        }
+    }
+
+    Some(if result & 1 == 1 { !(result >> 1) } else { result >> 1 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Google's documented example: (38.5, -120.2), (40.7, -120.95), (43.252, -126.453)
+    const EXPECTED: [[f64; 2]; 3] = [[-120.2, 38.5], [-120.95, 40.7], [-126.453, 43.252]];
+
+    fn assert_points(actual: &[[f64; 2]], expected: &[[f64; 2]]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a[0] - e[0]).abs() < 1e-9 && (a[1] - e[1]).abs() < 1e-9, "{:?} != {:?}", a, e);
+        }
+    }
+
+    #[test]
+    fn decodes_precision_5() {
+        let points = decode_polyline("_p~iF~ps|U_ulLnnqC_mqNvxq`@", 5).unwrap();
+        assert_points(&points, &EXPECTED);
+    }
+
+    #[test]
+    fn decodes_precision_6() {
+        let points = decode_polyline("_izlhA~rlgdF_{geC~ywl@_kwzCn`{nI", 6).unwrap();
+        assert_points(&points, &EXPECTED);
+    }
+
+    #[test]
+    fn empty_polyline_has_no_points() {
+        assert_eq!(decode_polyline("", 5), Some(Vec::new()));
+    }
+
+    #[test]
+    fn rejects_truncated_varint() {
+        // The last character still has the continuation bit set
+        assert_eq!(decode_polyline("_p~iF~ps|", 5), None);
+    }
+
+    #[test]
+    fn rejects_odd_number_of_values() {
+        // A latitude without its longitude
+        assert_eq!(decode_polyline("_p~iF", 5), None);
+        assert_eq!(decode_polyline("_p~iF~ps|U_ulL", 5), None);
+    }
+
+    #[test]
+    fn rejects_characters_outside_the_alphabet() {
+        assert_eq!(decode_polyline("_p~iF ps|U", 5), None);
+        assert_eq!(decode_polyline("_p~iF\u{7f}ps|U", 5), None);
+        assert_eq!(decode_polyline("_p~iFé", 5), None);
+    }
+
+    #[test]
+    fn rejects_overlong_and_overflowing_values() {
+        assert_eq!(decode_polyline(&"~".repeat(20), 5), None);
+
+        // Latitude deltas of 2^62 - 1, so the running sum overflows on the third
+        let huge = format!("}}{}F?", "~".repeat(11));
+        assert_eq!(decode_polyline(&huge.repeat(4), 5), None);
+    }
+}
\ No newline at end of file
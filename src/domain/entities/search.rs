@@ -0,0 +1,294 @@
+use serde::{de::{DeserializeOwned, IntoDeserializer}, Deserialize, Deserializer, Serialize};
+use strum_macros::{Display, EnumString};
+use uuid::Uuid;
+
+use crate::domain::entities::{business::Business, category::BusinessCategory};
+
+/// Whether a business must match any or all of the requested values
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    #[default]
+    Any,
+    All,
+}
+
+/// Categories a search is restricted to
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CategoryFilter {
+    pub categories: Vec<BusinessCategory>,
+    pub mode: MatchMode,
+}
+
+impl CategoryFilter {
+    pub fn new(categories: Vec<BusinessCategory>, mode: MatchMode) -> Self {
+        let mut categories = categories;
+        categories.sort();
+        categories.dedup();
+        Self { categories, mode }
+    }
+
+    /// An empty filter doesn't restrict the search
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    pub fn category_names(&self) -> Vec<String> {
+        self.categories.iter().map(|c| c.to_string()).collect()
+    }
+}
+
+/// Business attributes a search is restricted to, on top of its categories.
+/// The default doesn't restrict anything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AttributeFilter {
+    /// Minimum average review score
+    pub min_rating: Option<f64>,
+    pub min_reviews: Option<i32>,
+    pub registered_only: bool,
+    pub specializations: Vec<String>,
+    /// Whether businesses need any or all of `specializations`
+    pub specialization_mode: MatchMode,
+}
+
+/// Free-text search, optionally biased towards a location
+#[derive(Debug, Clone)]
+pub struct TextSearchQuery {
+    pub text: String,
+    /// (latitude, longitude) that nearer results are ranked up around
+    pub location: Option<(f64, f64)>,
+    pub filter: CategoryFilter,
+    pub limit: i64,
+}
+
+/// Map viewport in WGS84 degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub min_lat: f64,
+    pub min_lon: f64,
+    pub max_lat: f64,
+    pub max_lon: f64,
+}
+
+/// Search results cut off at a cap
+#[derive(Debug, Clone)]
+pub struct CappedResults<T> {
+    pub items: Vec<T>,
+    /// More items matched than were returned
+    pub truncated: bool,
+}
+
+impl<T> CappedResults<T> {
+    /// Builds the results from a fetch of `cap + 1` rows
+    pub fn from_overfetch(mut items: Vec<T>, cap: usize) -> Self {
+        let truncated = items.len() > cap;
+        items.truncate(cap);
+        Self { items, truncated }
+    }
+}
+
+/// Spatial part of a search, shared by its results and facet counts
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchArea {
+    Radius {
+        /// (latitude, longitude) of the search point
+        location: (f64, f64),
+        radius_meters: f64,
+    },
+    BoundingBox(BoundingBox),
+}
+
+/// Number of matching businesses having a facet value
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FacetCount<T> {
+    pub value: T,
+    pub count: i64,
+}
+
+/// Result counts of a search by category, specialization and city, most
+/// frequent first. Category counts ignore the category filter so every filter
+/// chip can show what selecting it would give; the others apply all filters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SearchFacets {
+    pub categories: Vec<FacetCount<BusinessCategory>>,
+    pub specializations: Vec<FacetCount<String>>,
+    pub cities: Vec<FacetCount<String>>,
+}
+
+/// Where a search result lies relative to the search point
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Proximity {
+    pub distance_meters: f64,
+    /// Initial bearing from the search point, clockwise from north in [0, 360)
+    pub bearing_degrees: f64,
+}
+
+const COMPASS_POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+impl Proximity {
+    /// Distance rounded the way people read it: "40 m", "350 m", "1.2 km", "15 km"
+    pub fn display_distance(&self) -> String {
+        let meters = self.distance_meters.max(0.0);
+        if meters < 995.0 {
+            format!("{} m", ((meters / 10.0).round() * 10.0) as i64)
+        } else if meters < 9950.0 {
+            format!("{:.1} km", meters / 1000.0)
+        } else {
+            format!("{} km", (meters / 1000.0).round() as i64)
+        }
+    }
+
+    /// Eight-point compass direction of the bearing, e.g. "NE"
+    pub fn compass_point(&self) -> &'static str {
+        let sector = (self.bearing_degrees.rem_euclid(360.0) / 45.0).round() as usize % 8;
+        COMPASS_POINTS[sector]
+    }
+}
+
+/// Order of radius search results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SortOrder {
+    /// Nearest first
+    #[default]
+    Distance,
+    /// Highest Bayesian-adjusted rating first
+    Rating,
+    /// Most reviewed first
+    Reviews,
+    /// Alphabetical, unnamed businesses last
+    Name,
+    /// Blend of proximity, rating and registration, see `RankingWeights`
+    BestMatch,
+}
+
+/// Tuning for `SortOrder::Rating` and `SortOrder::BestMatch`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RankingWeights {
+    pub distance: f64,
+    pub rating: f64,
+    pub registered: f64,
+    /// Distance at which the proximity score has decayed to 1/e
+    pub distance_decay_meters: f64,
+    /// Ratings are pulled towards this mean...
+    pub rating_prior_mean: f64,
+    /// ...as strongly as this many reviews would
+    pub rating_prior_reviews: f64,
+}
+
+/// Radius search around a point
+#[derive(Debug, Clone)]
+pub struct RadiusSearchQuery {
+    /// (latitude, longitude) of the search point
+    pub location: (f64, f64),
+    pub radius_meters: f64,
+    pub filter: CategoryFilter,
+    pub attributes: AttributeFilter,
+    pub sort: SortOrder,
+    pub ranking: RankingWeights,
+    pub limit: i64,
+    /// Cursor of the previous page
+    pub after: Option<SearchCursor>,
+}
+
+/// The `limit` businesses nearest to a point, however far away
+#[derive(Debug, Clone)]
+pub struct NearestSearchQuery {
+    /// (latitude, longitude) of the search point
+    pub location: (f64, f64),
+    pub filter: CategoryFilter,
+    /// Optional hard cap on the distance in meters
+    pub max_distance_meters: Option<f64>,
+    pub limit: i64,
+}
+
+/// Most businesses returned per category by a nearest-by-category search
+pub const MAX_NEAREST_PER_CATEGORY: i64 = 10;
+
+/// The `per_category` businesses nearest to a point for each of several categories
+#[derive(Debug, Clone)]
+pub struct NearestByCategoryQuery {
+    /// (latitude, longitude) of the search point
+    pub location: (f64, f64),
+    pub categories: Vec<BusinessCategory>,
+    pub per_category: i64,
+    /// Optional hard cap on the distance in meters
+    pub max_distance_meters: Option<f64>,
+}
+
+/// Nearest businesses having one category, nearest first
+#[derive(Debug, Clone)]
+pub struct CategoryNearest {
+    pub category: BusinessCategory,
+    pub businesses: Vec<Business>,
+}
+
+/// Position after the last result of a page: the sort key and id of that
+/// result. Clients get it as an opaque hex token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchCursor {
+    pub sort: SortOrder,
+    /// Sort key of the last result as rendered by the database
+    pub key: String,
+    pub id: Uuid,
+}
+
+impl SearchCursor {
+    pub fn encode(&self) -> String {
+        format!("{}:{}:{}", self.sort, self.id, self.key)
+            .bytes()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// None if the token wasn't produced by `encode`. Keys of numeric sort
+    /// orders must be finite numbers, since they're cast to float8 in SQL.
+    pub fn decode(token: &str) -> Option<Self> {
+        if !token.len().is_multiple_of(2) || !token.is_ascii() {
+            return None;
+        }
+        let bytes = (0..token.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&token[i..i + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let decoded = String::from_utf8(bytes).ok()?;
+
+        let mut parts = decoded.splitn(3, ':');
+        let sort = parts.next()?.parse().ok()?;
+        let id = parts.next()?.parse().ok()?;
+        let key = parts.next()?.to_string();
+        if sort != SortOrder::Name && !key.parse::<f64>().is_ok_and(f64::is_finite) {
+            return None;
+        }
+        Some(Self { sort, key, id })
+    }
+}
+
+/// One page of search results
+#[derive(Debug, Clone)]
+pub struct SearchPage<T> {
+    pub items: Vec<T>,
+    /// Where the next page starts, None on the last page
+    pub next_cursor: Option<SearchCursor>,
+    /// Number of matches across all pages, counted up to a cap
+    pub total_estimate: i64,
+}
+
+/// Deserializes a comma-separated query parameter, e.g. `categories=CarWash,GasStation`
+pub fn comma_separated<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let raw: Option<String> = Option::deserialize(deserializer)?;
+
+    raw.map(|raw| {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| T::deserialize(IntoDeserializer::<D::Error>::into_deserializer(item)))
+            .collect()
+    })
+    .transpose()
+}
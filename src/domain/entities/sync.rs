@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::business::BusinessInsert;
+
+/// Outcome of an Overpass sync
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncStats {
+    /// Businesses inserted or updated
+    pub synced: usize,
+    /// OSM elements owned by a registered business and left untouched
+    pub skipped_registered: usize,
+    /// Synced businesses with at least one category inferred from the name
+    pub with_inferred_categories: usize,
+    /// Synced businesses whose categories all come from the name
+    pub inferred_only: usize,
+    /// Name-inferred category assignments per category
+    pub inferred_by_category: BTreeMap<String, usize>,
+}
+
+impl SyncStats {
+    pub fn record_synced(&mut self, business: &BusinessInsert) {
+        self.synced += 1;
+
+        if business.inferred_categories.is_empty() {
+            return;
+        }
+
+        self.with_inferred_categories += 1;
+        if business.inferred_categories.len() == business.categories.len() {
+            self.inferred_only += 1;
+        }
+        for category in &business.inferred_categories {
+            *self.inferred_by_category.entry(category.to_string()).or_default() += 1;
+        }
+    }
+}
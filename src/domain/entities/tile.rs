@@ -0,0 +1,33 @@
+use std::str::FromStr;
+
+/// Deepest zoom level tiles are served for
+pub const MAX_TILE_ZOOM: u32 = 22;
+
+/// Name of the layer businesses are encoded into
+pub const BUSINESS_TILE_LAYER: &str = "businesses";
+
+/// Tile coordinates in the XYZ (web mercator) scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileCoordinates {
+    pub z: u32,
+    pub x: u32,
+    pub y: u32,
+}
+
+impl TileCoordinates {
+    /// Builds tile coordinates from path segments; `y` may carry a `.mvt` extension.
+    /// Returns None for coordinates outside the tile grid.
+    pub fn from_path(z: u32, x: u32, y: &str) -> Option<Self> {
+        let y = u32::from_str(y.strip_suffix(".mvt").unwrap_or(y)).ok()?;
+        let tile = Self { z, x, y };
+        tile.is_valid().then_some(tile)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        if self.z > MAX_TILE_ZOOM {
+            return false;
+        }
+        let tiles_per_side = 1u64 << self.z;
+        u64::from(self.x) < tiles_per_side && u64::from(self.y) < tiles_per_side
+    }
+}
@@ -1,23 +1,37 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 use crate::application::handlers::business::CreateUserBusinessRequest;
-use crate::domain::entities::business::{Business};
+use crate::domain::entities::area::AreaSearchQuery;
+use crate::domain::entities::autocomplete::AutocompleteSuggestion;
+use crate::domain::entities::business::{Business, BusinessBatch};
+use crate::domain::entities::cluster::ClusteredBusinesses;
+use crate::domain::entities::data_quality::{DataQualityCriteria, DataQualityIssue, DataQualityReport};
+use crate::domain::entities::duplicate::DuplicateCandidate;
+use crate::domain::entities::geojson::Geometry;
+use crate::domain::entities::route::RouteSearchQuery;
+use crate::domain::entities::search::{AttributeFilter, BoundingBox, CategoryFilter, CategoryNearest, NearestByCategoryQuery, NearestSearchQuery, RadiusSearchQuery, SearchArea, SearchFacets, SearchPage, TextSearchQuery};
+use crate::domain::entities::sync::SyncStats;
+use crate::domain::entities::tile::TileCoordinates;
 use crate::infrastructure::external::overpass::OverpassElement;
 use crate::shared::error::Result;
 use crate::domain::entities::category::BusinessCategory;
+use crate::domain::entities::city::City;
 
 #[async_trait]
 pub trait BusinessRepository: Send + Sync {
     /// Sync businesses from Overpass elements to the database.
     /// This method processes each element, checks if it is car-related,
     /// and inserts or updates the business in the database.
+    /// Returns sync statistics, with name-inferred categories reported separately.
     async fn sync_from_overpass_elements(
         &self,
         elements: Vec<OverpassElement>,
-    ) -> Result<usize>;
+    ) -> Result<SyncStats>;
 
     /// Sync a user-created business to the database.
     /// This method takes a request containing business details and inserts it into the database.
+    /// When the request claims an existing business or OSM id, the registered row takes over
+    /// that OSM identity so later Overpass syncs don't re-create the original entry.
     async fn sync_user_business(
         &self,
         req: CreateUserBusinessRequest,
@@ -25,16 +39,155 @@ pub trait BusinessRepository: Send + Sync {
 
     /// Get a business by its ID.
     /// This method retrieves a business from the database using its unique identifier.
+    /// Ids retired by a merge or claim resolve to the business that replaced them.
     async fn get_business_by_id(&self, id: Uuid) -> Result<Option<Business>>;
 
-    /// Search for businesses within a specified radius and category.
-    /// This method retrieves businesses that are within a certain distance from a given point
-    async fn get_businesses_by_location_and_category(
+    /// Full details of the businesses with the given ids, following redirects of
+    /// retired ids like `get_business_by_id`.
+    async fn get_business_details(&self, ids: &[Uuid]) -> Result<BusinessBatch>;
+
+    /// Search for businesses within a specified radius matching a category filter.
+    /// This method retrieves businesses that are within a certain distance from a given point,
+    /// having any or all of the filter's categories, in the query's sort order.
+    /// Results are paged by (sort key, id); `query.after` is the cursor of the previous page.
+    async fn get_businesses_by_location_and_categories(
+        &self,
+        query: &RadiusSearchQuery,
+    ) -> Result<SearchPage<Business>>;
+
+    /// The businesses nearest to a point matching a category filter, nearest
+    /// first, with no radius unless `query.max_distance_meters` is set.
+    async fn get_nearest_businesses(&self, query: &NearestSearchQuery) -> Result<Vec<Business>>;
+
+    /// The nearest businesses for each requested category, in request order. A
+    /// category with no business in range gets an empty list.
+    async fn get_nearest_businesses_by_category(
+        &self,
+        query: &NearestByCategoryQuery,
+    ) -> Result<Vec<CategoryNearest>>;
+
+    /// Facet counts of the businesses in a search area matching category and
+    /// attribute filters, computed in a single query.
+    async fn get_search_facets(
+        &self,
+        area: &SearchArea,
+        filter: &CategoryFilter,
+        attributes: &AttributeFilter,
+    ) -> Result<SearchFacets>;
+
+    /// Businesses inside a map viewport matching category and attribute filters
+    /// (empty filters match all). Registered and most-reviewed businesses come first so a
+    /// capped result keeps the most relevant ones.
+    async fn get_businesses_in_bbox(
+        &self,
+        bbox: &BoundingBox,
+        filter: &CategoryFilter,
+        attributes: &AttributeFilter,
+        limit: i64,
+    ) -> Result<Vec<Business>>;
+
+    /// Businesses inside a map viewport snapped to a grid of `cell_degrees` cells.
+    /// Cells holding at least `min_cluster_size` businesses come back as clusters,
+    /// the businesses of sparser cells individually.
+    async fn get_business_clusters(
+        &self,
+        bbox: &BoundingBox,
+        cell_degrees: f64,
+        filter: &CategoryFilter,
+        min_cluster_size: i64,
+    ) -> Result<ClusteredBusinesses>;
+
+    /// Businesses in a web map tile encoded as a Mapbox Vector Tile with a single
+    /// `businesses` layer. Empty tiles come back as an empty buffer.
+    async fn get_business_tile(
+        &self,
+        tile: &TileCoordinates,
+        filter: &CategoryFilter,
+    ) -> Result<Vec<u8>>;
+
+    /// Businesses within `query.corridor_meters` of a route matching a category
+    /// filter, in the order they're reached along the route.
+    async fn get_businesses_along_route(&self, query: &RouteSearchQuery) -> Result<Vec<Business>>;
+
+    /// Geodesic area of a Polygon or MultiPolygon in square kilometers.
+    async fn get_area_sq_km(&self, area: &Geometry) -> Result<f64>;
+
+    /// Businesses inside a Polygon or MultiPolygon (boundary included) matching
+    /// a category filter, registered and most-reviewed businesses first.
+    async fn get_businesses_in_area(&self, query: &AreaSearchQuery) -> Result<Vec<Business>>;
+
+    /// Fuzzy search over names, addresses, cities and specializations, tolerant of
+    /// typos and Cyrillic/Latin mixing. Ranked by text relevance, blended with
+    /// proximity when the query has a location.
+    async fn search_businesses_by_text(&self, query: &TextSearchQuery) -> Result<Vec<Business>>;
+
+    /// Business name, city and specialization suggestions for a typed prefix.
+    /// Business names are biased towards `location`; at most `limit` of each kind.
+    async fn autocomplete(
+        &self,
+        text: &str,
+        location: Option<(f64, f64)>,
+        limit: i64,
+    ) -> Result<Vec<AutocompleteSuggestion>>;
+
+    /// Rebuild the city and specialization terms used by autocomplete.
+    async fn refresh_autocomplete_terms(&self) -> Result<()>;
+
+    /// Rebuild the city directory.
+    async fn refresh_cities(&self) -> Result<()>;
+
+    /// Every city with businesses, those with the most businesses first.
+    async fn list_cities(&self) -> Result<Vec<City>>;
+
+    async fn get_city(&self, slug: &str) -> Result<Option<City>>;
+
+    /// Businesses in the city with the given slug matching a category filter,
+    /// registered and most-reviewed businesses first.
+    async fn get_businesses_in_city(
+        &self,
+        slug: &str,
+        filter: &CategoryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Business>>;
+
+    /// Flag pairs of businesses within `max_distance_meters` of each other that share
+    /// a category and whose normalized names are at least `min_name_similarity` alike.
+    /// Replaces the previous candidate list and returns the number of flagged pairs.
+    async fn detect_duplicates(
+        &self,
+        max_distance_meters: f64,
+        min_name_similarity: f32,
+    ) -> Result<u64>;
+
+    /// List flagged duplicate pairs, most similar first.
+    async fn get_duplicate_candidates(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<DuplicateCandidate>>;
+
+    /// Merge `retire_id` into `keep_id`: categories and specializations are combined,
+    /// the retired row is deleted and its id redirects to the kept business.
+    async fn merge_businesses(&self, keep_id: Uuid, retire_id: Uuid) -> Result<Business>;
+
+    /// Count data quality issues overall and per category and city, with one
+    /// page of the groups.
+    async fn get_data_quality_report(
+        &self,
+        criteria: &DataQualityCriteria,
+        limit: i64,
+        offset: i64,
+    ) -> Result<DataQualityReport>;
+
+    /// List businesses affected by a data quality issue, least recently updated first.
+    async fn get_businesses_with_issue(
         &self,
-        latitude: f64,
-        longitude: f64,
-        radius_km: i32,
-        category: &BusinessCategory,
+        issue: DataQualityIssue,
+        criteria: &DataQualityCriteria,
+        category: Option<&BusinessCategory>,
+        city: Option<&str>,
         limit: i64,
+        offset: i64,
     ) -> Result<Vec<Business>>;
 }
\ No newline at end of file
@@ -1,11 +1,37 @@
 use std::sync::Arc;
+use uuid::Uuid;
 use crate::application::handlers::business::CreateUserBusinessRequest;
-use crate::domain::entities::business::Business;
+use crate::domain::entities::area::{AreaSearchQuery, MAX_AREA_SQ_KM};
+use crate::domain::entities::autocomplete::AutocompleteSuggestion;
+use crate::domain::entities::business::{Business, BusinessBatch, BusinessDetails};
 use crate::domain::entities::category::BusinessCategory;
+use crate::domain::entities::city::City;
+use crate::domain::entities::cluster::{clustering_zoom, grid_cell_degrees, ClusteredBusinesses, MIN_CLUSTER_SIZE};
+use crate::domain::entities::data_quality::{DataQualityCriteria, DataQualityIssue, DataQualityReport};
+use crate::domain::entities::duplicate::DuplicateCandidate;
+use crate::domain::entities::route::RouteSearchQuery;
+use crate::domain::entities::search::{
+    AttributeFilter, BoundingBox, CappedResults, CategoryFilter, CategoryNearest, NearestByCategoryQuery,
+    NearestSearchQuery, RadiusSearchQuery, SearchArea, SearchFacets, SearchPage, TextSearchQuery,
+};
+use crate::domain::entities::sync::SyncStats;
+use crate::domain::entities::tile::TileCoordinates;
 use crate::domain::repositories::business_repository::BusinessRepository;
 use crate::infrastructure::external::overpass::{OverpassQuery, OverpassService};
 use crate::shared::error::{AppError, Result};
 
+/// Most businesses returned for a map viewport
+pub const MAX_BBOX_RESULTS: i64 = 500;
+
+/// Most businesses returned for a search area
+pub const MAX_AREA_RESULTS: i64 = 500;
+
+/// Most businesses returned per page of a city listing
+pub const MAX_CITY_RESULTS: i64 = 200;
+
+/// Categories shown before any stored suggestion in autocomplete
+const MAX_CATEGORY_SUGGESTIONS: usize = 3;
+
 pub struct BusinessService {
     business_repository: Arc<dyn BusinessRepository>,
     overpass_service: Arc<OverpassService>,
@@ -22,7 +48,7 @@ impl BusinessService {
         }
     }
 
-    pub async fn sync_businesses(&self, country_code: &str) -> Result<usize> {
+    pub async fn sync_businesses(&self, country_code: &str) -> Result<SyncStats> {
         tracing::info!("Starting business sync for country: {}", country_code);
         
         // Create Overpass query
@@ -35,12 +61,25 @@ impl BusinessService {
             .map_err(|e| AppError::OsmParsing(format!("Error fetching data from overpass: {}", e)))?;
 
         // Sync businesses to database
-        let synced_count = self.business_repository
+        let stats = self.business_repository
             .sync_from_overpass_elements(elements)
             .await?;
 
-        tracing::info!("Successfully synced {} businesses", synced_count);
-        Ok(synced_count)
+        tracing::info!(
+            "Successfully synced {} businesses ({} with name-inferred categories, {} inferred only)",
+            stats.synced,
+            stats.with_inferred_categories,
+            stats.inferred_only
+        );
+
+        if let Err(e) = self.business_repository.refresh_autocomplete_terms().await {
+            tracing::error!("Failed to refresh autocomplete terms: {}", e);
+        }
+        if let Err(e) = self.business_repository.refresh_cities().await {
+            tracing::error!("Failed to refresh city directory: {}", e);
+        }
+
+        Ok(stats)
     }
 
     pub async fn create_user_business(&self, req: CreateUserBusinessRequest) -> Result<Business> {
@@ -49,21 +88,270 @@ impl BusinessService {
             .await
     }
 
-    pub async fn search_businesses_by_radius_and_category(
+    pub async fn get_business_details(&self, id: Uuid) -> Result<BusinessDetails> {
+        let batch = self.business_repository
+            .get_business_details(&[id])
+            .await?;
+
+        batch.businesses
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::NotFound(format!("business {}", id)))
+    }
+
+    pub async fn get_business_batch(&self, ids: &[Uuid]) -> Result<BusinessBatch> {
+        self.business_repository
+            .get_business_details(ids)
+            .await
+    }
+
+    pub async fn search_businesses_by_radius_and_categories(
+        &self,
+        query: &RadiusSearchQuery,
+    ) -> Result<SearchPage<Business>> {
+        self.business_repository
+            .get_businesses_by_location_and_categories(query)
+            .await
+    }
+
+    pub async fn search_nearest_businesses(&self, query: &NearestSearchQuery) -> Result<Vec<Business>> {
+        self.business_repository
+            .get_nearest_businesses(query)
+            .await
+    }
+
+    pub async fn search_nearest_businesses_by_category(
+        &self,
+        query: &NearestByCategoryQuery,
+    ) -> Result<Vec<CategoryNearest>> {
+        self.business_repository
+            .get_nearest_businesses_by_category(query)
+            .await
+    }
+
+    pub async fn search_facets(
+        &self,
+        area: &SearchArea,
+        filter: &CategoryFilter,
+        attributes: &AttributeFilter,
+    ) -> Result<SearchFacets> {
+        self.business_repository
+            .get_search_facets(area, filter, attributes)
+            .await
+    }
+
+    /// Businesses in a map viewport, capped at `limit` (at most
+    /// `MAX_BBOX_RESULTS`) with a flag when more matched
+    pub async fn search_businesses_in_bbox(
+        &self,
+        bbox: &BoundingBox,
+        filter: &CategoryFilter,
+        attributes: &AttributeFilter,
+        limit: i64,
+    ) -> Result<CappedResults<Business>> {
+        let cap = limit.clamp(1, MAX_BBOX_RESULTS);
+
+        let businesses = self.business_repository
+            .get_businesses_in_bbox(bbox, filter, attributes, cap + 1)
+            .await?;
+
+        Ok(CappedResults::from_overfetch(businesses, cap as usize))
+    }
+
+    /// Map markers for a viewport at a zoom level: clusters where businesses are
+    /// dense, individual businesses where they're sparse. Large viewports are
+    /// clustered on a coarser grid so the marker count stays bounded.
+    pub async fn cluster_businesses(
+        &self,
+        bbox: &BoundingBox,
+        zoom: u8,
+        filter: &CategoryFilter,
+    ) -> Result<ClusteredBusinesses> {
+        let zoom = clustering_zoom(bbox, zoom);
+        let cell_degrees = grid_cell_degrees(zoom);
+
+        let clustered = self.business_repository
+            .get_business_clusters(bbox, cell_degrees, filter, MIN_CLUSTER_SIZE)
+            .await?;
+
+        Ok(ClusteredBusinesses {
+            zoom,
+            cell_degrees,
+            ..clustered
+        })
+    }
+
+    pub async fn get_business_tile(
+        &self,
+        tile: &TileCoordinates,
+        filter: &CategoryFilter,
+    ) -> Result<Vec<u8>> {
+        self.business_repository
+            .get_business_tile(tile, filter)
+            .await
+    }
+
+    pub async fn search_businesses_along_route(
+        &self,
+        query: &RouteSearchQuery,
+    ) -> Result<Vec<Business>> {
+        self.business_repository
+            .get_businesses_along_route(query)
+            .await
+    }
+
+    /// Businesses inside a polygon, capped at `query.limit` (at most
+    /// `MAX_AREA_RESULTS`). Areas over `MAX_AREA_SQ_KM` are rejected.
+    pub async fn search_businesses_in_area(
+        &self,
+        query: &AreaSearchQuery,
+    ) -> Result<CappedResults<Business>> {
+        let sq_km = self.business_repository
+            .get_area_sq_km(&query.area)
+            .await?;
+        if sq_km > MAX_AREA_SQ_KM {
+            return Err(AppError::validation(
+                "geometry",
+                format!("area is {:.0} km², at most {:.0} km² is allowed", sq_km, MAX_AREA_SQ_KM),
+            ));
+        }
+
+        let cap = query.limit.clamp(1, MAX_AREA_RESULTS);
+        let businesses = self.business_repository
+            .get_businesses_in_area(&AreaSearchQuery { limit: cap + 1, ..query.clone() })
+            .await?;
+
+        Ok(CappedResults::from_overfetch(businesses, cap as usize))
+    }
+
+    pub async fn search_businesses_by_text(&self, query: &TextSearchQuery) -> Result<Vec<Business>> {
+        self.business_repository
+            .search_businesses_by_text(query)
+            .await
+    }
+
+    /// Mixed suggestions for the search bar: categories first, then cities,
+    /// business names and specializations
+    pub async fn autocomplete(
+        &self,
+        text: &str,
+        location: Option<(f64, f64)>,
+        limit: i64,
+    ) -> Result<Vec<AutocompleteSuggestion>> {
+        let limit = limit.max(1) as usize;
+
+        let mut suggestions: Vec<AutocompleteSuggestion> =
+            AutocompleteSuggestion::matching_categories(text)
+                .into_iter()
+                .take(MAX_CATEGORY_SUGGESTIONS)
+                .collect();
+
+        let mut stored = self.business_repository
+            .autocomplete(text, location, limit as i64)
+            .await?;
+        stored.sort_by_key(|s| match s {
+            AutocompleteSuggestion::City { .. } => 0,
+            AutocompleteSuggestion::Business { .. } => 1,
+            _ => 2,
+        });
+
+        suggestions.extend(stored);
+        suggestions.truncate(limit);
+        Ok(suggestions)
+    }
+
+    pub async fn detect_duplicates(
+        &self,
+        max_distance_meters: f64,
+        min_name_similarity: f32,
+    ) -> Result<u64> {
+        tracing::info!(
+            "Detecting duplicate businesses within {}m and name similarity >= {}",
+            max_distance_meters,
+            min_name_similarity
+        );
+
+        let candidates = self.business_repository
+            .detect_duplicates(max_distance_meters, min_name_similarity)
+            .await?;
+
+        tracing::info!("Flagged {} duplicate candidates", candidates);
+        Ok(candidates)
+    }
+
+    pub async fn get_duplicate_candidates(
+        &self,
+        limit: i64,
+        offset: Option<i64>,
+    ) -> Result<Vec<DuplicateCandidate>> {
+        self.business_repository
+            .get_duplicate_candidates(limit, offset.unwrap_or(0))
+            .await
+    }
+
+    pub async fn merge_businesses(&self, keep_id: Uuid, retire_id: Uuid) -> Result<Business> {
+        self.business_repository
+            .merge_businesses(keep_id, retire_id)
+            .await
+    }
+
+    pub async fn get_data_quality_report(
+        &self,
+        criteria: &DataQualityCriteria,
+        limit: i64,
+        offset: Option<i64>,
+    ) -> Result<DataQualityReport> {
+        self.business_repository
+            .get_data_quality_report(criteria, limit, offset.unwrap_or(0))
+            .await
+    }
+
+    pub async fn list_cities(&self) -> Result<Vec<City>> {
+        self.business_repository
+            .list_cities()
+            .await
+    }
+
+    pub async fn get_city(&self, slug: &str) -> Result<Option<City>> {
+        self.business_repository
+            .get_city(slug)
+            .await
+    }
+
+    /// A page of businesses in a city, capped at `limit` (at most
+    /// `MAX_CITY_RESULTS`) with a flag when more follow
+    pub async fn get_businesses_in_city(
+        &self,
+        slug: &str,
+        filter: &CategoryFilter,
+        limit: i64,
+        offset: Option<i64>,
+    ) -> Result<CappedResults<Business>> {
+        let cap = limit.clamp(1, MAX_CITY_RESULTS);
+        let businesses = self.business_repository
+            .get_businesses_in_city(slug, filter, cap + 1, offset.unwrap_or(0))
+            .await?;
+
+        Ok(CappedResults::from_overfetch(businesses, cap as usize))
+    }
+
+    pub async fn get_businesses_with_issue(
         &self,
-        latitude: f64,
-        longitude: f64,
-        radius_km: i32,
-        category: &BusinessCategory,
-        limit: Option<i64>,
+        issue: DataQualityIssue,
+        criteria: &DataQualityCriteria,
+        category: Option<&BusinessCategory>,
+        city: Option<&str>,
+        limit: i64,
+        offset: Option<i64>,
     ) -> Result<Vec<Business>> {
         self.business_repository
-            .get_businesses_by_location_and_category(
-                latitude,
-                longitude,
-                radius_km,
+            .get_businesses_with_issue(
+                issue,
+                criteria,
                 category,
-                limit.unwrap_or(50),
+                city,
+                limit,
+                offset.unwrap_or(0),
             )
             .await
     }
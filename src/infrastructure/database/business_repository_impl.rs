@@ -1,12 +1,35 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgRow;
+use sqlx::types::Json;
+use sqlx::{FromRow, PgPool, Postgres, Row, Transaction};
+use strum::IntoEnumIterator;
 use uuid::Uuid;
 use crate::application::handlers::business::CreateUserBusinessRequest;
-use crate::domain::entities::category::BusinessCategory;
+use crate::domain::entities::category::{BusinessCategory, PRIMARY_CATEGORY_ORDER};
 use crate::domain::repositories::business_repository::BusinessRepository;
-use crate::domain::entities::business::{Business, BusinessInsert};
+use crate::domain::entities::area::AreaSearchQuery;
+use crate::domain::entities::autocomplete::AutocompleteSuggestion;
+use crate::domain::entities::business::{Business, BusinessBatch, BusinessDetails, BusinessInsert};
+use crate::domain::entities::city::City;
+use crate::domain::entities::cluster::{BusinessCluster, ClusteredBusinesses};
+use crate::domain::entities::data_quality::{
+    DataQualityCounts, DataQualityCriteria, DataQualityGroup, DataQualityIssue, DataQualityReport,
+};
+use crate::domain::entities::duplicate::{DuplicateCandidate, DuplicateSide};
+use crate::domain::entities::geojson::Geometry;
+use crate::domain::entities::route::{RoutePosition, RouteSearchQuery};
+use crate::domain::entities::search::{
+    AttributeFilter, BoundingBox, CategoryFilter, CategoryNearest, FacetCount, MatchMode, NearestByCategoryQuery,
+    NearestSearchQuery, Proximity, RadiusSearchQuery, SearchArea, SearchCursor, SearchFacets, SearchPage, SortOrder,
+    TextSearchQuery,
+};
+use crate::domain::entities::sync::SyncStats;
+use crate::domain::entities::tile::{TileCoordinates, BUSINESS_TILE_LAYER};
 use crate::infrastructure::external::overpass::OverpassElement;
-use crate::shared::error::{Result};
+use crate::shared::error::{AppError, Result};
 use num_traits::cast::ToPrimitive;
 
 pub struct PostgresBusinessRepository {
@@ -24,10 +47,17 @@ impl BusinessRepository for PostgresBusinessRepository {
     async fn sync_from_overpass_elements(
         &self,
         elements: Vec<OverpassElement>,
-    ) -> Result<usize> {
-        let mut synced_count = 0;
+    ) -> Result<SyncStats> {
+        let mut stats = SyncStats::default();
+        let retired_osm_ids = self.get_retired_osm_ids().await?;
 
         for element in elements {
+            // Skip OSM objects that were merged into another business
+            if retired_osm_ids.contains(&element.id) {
+                tracing::debug!("Skipping element {} merged into another business", element.id);
+                continue;
+            }
+
             // Skip elements without coordinates or tags
             let (lat, lon) = match (element.lat, element.lon) {
                 (Some(lat), Some(lon)) => (lat, lon),
@@ -52,19 +82,23 @@ impl BusinessRepository for PostgresBusinessRepository {
             );
 
             match self.upsert_business(&business_insert).await {
-                Ok(_) => {
-                    synced_count += 1;
-                    if synced_count % 100 == 0 {
-                        tracing::info!("Synced {} businesses so far", synced_count);
+                Ok(Some(_)) => {
+                    stats.record_synced(&business_insert);
+                    if stats.synced % 100 == 0 {
+                        tracing::info!("Synced {} businesses so far", stats.synced);
                     }
                 }
+                Ok(None) => {
+                    stats.skipped_registered += 1;
+                    tracing::debug!("OSM element {} is claimed by a registered business, skipping", element.id);
+                }
                 Err(e) => {
                     tracing::error!("Failed to upsert business {}: {}", element.id, e);
                 }
             }
         }
 
-        Ok(synced_count)
+        Ok(stats)
     }
 
     async fn sync_user_business(
@@ -72,18 +106,31 @@ impl BusinessRepository for PostgresBusinessRepository {
         req: CreateUserBusinessRequest,
     ) -> Result<Business> {
         let id = req.id;
-        let specialization = &req.specializations.unwrap_or(Vec::new());
+        let mut tx = self.pool.begin().await?;
+
+        let (osm_id, claimed_id) = Self::claim_osm_identity(
+            &mut tx,
+            id,
+            req.claim_business_id,
+            req.claim_osm_id,
+        ).await?;
 
-        sqlx::query!(
+        let specialization = req.specializations.unwrap_or_default();
+        let categories: Vec<String> = req.categories.iter().map(|c| c.to_string()).collect();
+
+        sqlx::query(
             r#"
             INSERT INTO search.businesses (
-            id, name, name_en, address, location, categories, specializations, is_registered, city, logo_map_url, average_reviews, review_count
+            id, osm_id, name, name_en, address, location, categories, specializations, is_registered, city, logo_map_url, average_reviews, review_count,
+            phone, email, website, opening_hours
             ) VALUES (
-            $1, $2, $3, $4,
-            ST_SetSRID(ST_MakePoint($5, $6), 4326),
-            $7::search.business_category[], $8, TRUE, $9, $10, $11, $12
+            $1, $2, $3, $4, $5,
+            ST_SetSRID(ST_MakePoint($6, $7), 4326),
+            $8::text[]::search.business_category[], $9, TRUE, $10, $11, $12, $13,
+            $14, $15, $16, $17
             )
             ON CONFLICT (id) DO UPDATE SET
+            osm_id = COALESCE(EXCLUDED.osm_id, search.businesses.osm_id),
             name = EXCLUDED.name,
             name_en = EXCLUDED.name_en,
             address = EXCLUDED.address,
@@ -94,23 +141,49 @@ impl BusinessRepository for PostgresBusinessRepository {
             city = EXCLUDED.city,
             logo_map_url = EXCLUDED.logo_map_url,
             average_reviews = EXCLUDED.average_reviews,
-            review_count = EXCLUDED.review_count
+            review_count = EXCLUDED.review_count,
+            phone = EXCLUDED.phone,
+            email = EXCLUDED.email,
+            website = EXCLUDED.website,
+            opening_hours = EXCLUDED.opening_hours
             "#,
-            id,
-            req.name,
-            req.name_en,
-            req.address,
-            req.longitude,
-            req.latitude,
-            &req.categories as &[BusinessCategory],
-            specialization as &[String],
-            req.city,
-            req.logo_map_url,
-            req.average_reviews,
-            req.review_count.unwrap_or(0)
         )
-        .execute(&self.pool)
-        .await?;
+        .bind(id)
+        .bind(osm_id)
+        .bind(req.name)
+        .bind(req.name_en)
+        .bind(req.address)
+        .bind(req.longitude)
+        .bind(req.latitude)
+        .bind(categories)
+        .bind(specialization)
+        .bind(req.city)
+        .bind(req.logo_map_url)
+        .bind(req.average_reviews)
+        .bind(req.review_count.unwrap_or(0))
+        .bind(req.phone)
+        .bind(req.email)
+        .bind(req.website)
+        .bind(req.opening_hours)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            // Two registrations claiming an OSM id that was not imported yet
+            let osm_id_taken = e.as_database_error()
+                .and_then(|e| e.constraint())
+                == Some("unique_osm_id_type");
+            if osm_id_taken {
+                AppError::Conflict(format!("OSM id {:?} is already claimed", osm_id))
+            } else {
+                e.into()
+            }
+        })?;
+
+        if let Some(claimed_id) = claimed_id {
+            Self::retire_business(&mut tx, claimed_id, id, None).await?;
+        }
+
+        tx.commit().await?;
 
         self.get_business_by_id(id)
             .await?
@@ -118,6 +191,8 @@ impl BusinessRepository for PostgresBusinessRepository {
     }
 
     async fn get_business_by_id(&self, id: Uuid) -> Result<Option<Business>> {
+        let id = self.resolve_business_id(id).await?;
+
         let business = sqlx::query!(
             r#"
             SELECT 
@@ -162,121 +237,1806 @@ impl BusinessRepository for PostgresBusinessRepository {
             city: row.city,
             average_reviews: row.average_reviews.to_f64().unwrap_or(0.0),
             review_count: row.review_count,
+            proximity: None,
+            route_position: None,
         }))
     }
 
-    async fn get_businesses_by_location_and_category(
+    async fn get_business_details(&self, ids: &[Uuid]) -> Result<BusinessBatch> {
+        let sql = format!(
+            r#"
+            SELECT
+                requested.id AS requested_id,
+                {},
+                b.phone,
+                b.email,
+                b.website,
+                b.opening_hours,
+                b.inferred_categories::text[] AS inferred_categories
+            FROM unnest($1::uuid[]) AS requested(id)
+            LEFT JOIN search.business_redirects r ON r.retired_id = requested.id
+            JOIN search.businesses b ON b.id = COALESCE(r.business_id, requested.id)
+            "#,
+            BUSINESS_COLUMNS
+        );
+
+        let rows: Vec<BusinessDetailsRow> = sqlx::query_as(&sql)
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut found: HashMap<Uuid, BusinessDetailsRow> = rows
+            .into_iter()
+            .map(|row| (row.requested_id, row))
+            .collect();
+
+        let mut batch = BusinessBatch::default();
+        let mut requested = HashSet::new();
+        let mut returned = HashSet::new();
+        for id in ids {
+            if !requested.insert(*id) {
+                continue;
+            }
+            match found.remove(id) {
+                Some(row) => {
+                    if returned.insert(row.business.id) {
+                        batch.businesses.push(row.into());
+                    }
+                }
+                None => batch.missing.push(*id),
+            }
+        }
+
+        Ok(batch)
+    }
+
+    async fn get_businesses_by_location_and_categories(
+        &self,
+        query: &RadiusSearchQuery,
+    ) -> Result<SearchPage<Business>> {
+        let (lat, lon) = query.location;
+        let ranking = &query.ranking;
+
+        let within = format!(
+            r#"
+            ST_DWithin(
+                b.location::geography,
+                ST_SetSRID(ST_MakePoint($2, $1), 4326)::geography,
+                $3
+            )
+            AND {}
+            AND {}
+            "#,
+            category_predicate(query.filter.mode, "$4"),
+            attribute_predicate(&query.attributes, 5)
+        );
+
+        // Keyset pagination: rows strictly after the cursor in (sort key, id)
+        // order, so pages stay stable while businesses are added or removed.
+        // The key goes to the client as text; float8 text output round-trips.
+        let (sort_value, sort_type) = sort_key(query.sort);
+        let sql = format!(
+            r#"
+            SELECT ranked.*, ranked.sort_value::text AS sort_key
+            FROM (
+                SELECT matched.*, {sort_value} AS sort_value
+                FROM (
+                    SELECT {columns}, {proximity}
+                    FROM search.businesses b
+                    WHERE {within}
+                ) matched
+            ) ranked
+            WHERE $10::text IS NULL OR (ranked.sort_value, ranked.id) > ($10::{sort_type}, $11)
+            ORDER BY ranked.sort_value, ranked.id
+            LIMIT $9
+            "#,
+            columns = BUSINESS_COLUMNS,
+            proximity = proximity_columns("$1", "$2"),
+        );
+
+        let mut rows: Vec<SortedBusinessRow> = sqlx::query_as(&sql)
+            .bind(lat)
+            .bind(lon)
+            .bind(query.radius_meters)
+            .bind(query.filter.category_names())
+            .bind(query.attributes.min_rating)
+            .bind(query.attributes.min_reviews)
+            .bind(query.attributes.registered_only)
+            .bind(&query.attributes.specializations)
+            .bind(query.limit + 1)
+            .bind(query.after.as_ref().map(|cursor| cursor.key.clone()))
+            .bind(query.after.as_ref().map(|cursor| cursor.id))
+            .bind(ranking.rating_prior_mean)
+            .bind(ranking.rating_prior_reviews)
+            .bind(ranking.distance)
+            .bind(ranking.rating)
+            .bind(ranking.registered)
+            .bind(ranking.distance_decay_meters)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let count_sql = format!(
+            r#"
+            SELECT count(*)
+            FROM (
+                SELECT 1
+                FROM search.businesses b
+                WHERE {}
+                LIMIT $9
+            ) matched
+            "#,
+            within
+        );
+
+        let total_estimate: i64 = sqlx::query_scalar(&count_sql)
+            .bind(lat)
+            .bind(lon)
+            .bind(query.radius_meters)
+            .bind(query.filter.category_names())
+            .bind(query.attributes.min_rating)
+            .bind(query.attributes.min_reviews)
+            .bind(query.attributes.registered_only)
+            .bind(&query.attributes.specializations)
+            .bind(MAX_TOTAL_ESTIMATE)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let has_more = rows.len() as i64 > query.limit;
+        rows.truncate(query.limit.max(0) as usize);
+        let next_cursor = rows
+            .last()
+            .filter(|_| has_more)
+            .map(|row| SearchCursor {
+                sort: query.sort,
+                key: row.sort_key.clone(),
+                id: row.business.business.id,
+            });
+
+        Ok(SearchPage {
+            items: rows.into_iter().map(|row| Business::from(row.business)).collect(),
+            next_cursor,
+            total_estimate,
+        })
+    }
+
+    async fn get_businesses_in_bbox(
         &self,
-        lat: f64,
-        lon: f64,
-        radius_km: i32,
-        category: &BusinessCategory,
+        bbox: &BoundingBox,
+        filter: &CategoryFilter,
+        attributes: &AttributeFilter,
         limit: i64,
     ) -> Result<Vec<Business>> {
-        let category_str = category.to_string();
+        // When the viewport holds more than `limit` businesses, keep the ones
+        // users care about most so the truncated map still looks right
+        let sql = format!(
+            r#"
+            SELECT {}
+            FROM search.businesses b
+            WHERE b.location && ST_MakeEnvelope($1, $2, $3, $4, 4326)
+            AND {}
+            AND {}
+            ORDER BY b.is_registered IS TRUE DESC, b.review_count DESC, b.id
+            LIMIT $10
+            "#,
+            BUSINESS_COLUMNS,
+            category_predicate(filter.mode, "$5"),
+            attribute_predicate(attributes, 6)
+        );
+
+        let rows: Vec<BusinessRow> = sqlx::query_as(&sql)
+            .bind(bbox.min_lon)
+            .bind(bbox.min_lat)
+            .bind(bbox.max_lon)
+            .bind(bbox.max_lat)
+            .bind(filter.category_names())
+            .bind(attributes.min_rating)
+            .bind(attributes.min_reviews)
+            .bind(attributes.registered_only)
+            .bind(&attributes.specializations)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
 
-        let businesses = sqlx::query!(
+        Ok(rows.into_iter().map(Business::from).collect())
+    }
+
+    async fn get_search_facets(
+        &self,
+        area: &SearchArea,
+        filter: &CategoryFilter,
+        attributes: &AttributeFilter,
+    ) -> Result<SearchFacets> {
+        let (spatial, first_param) = match area {
+            SearchArea::Radius { .. } => (
+                "ST_DWithin(b.location::geography, ST_SetSRID(ST_MakePoint($2, $1), 4326)::geography, $3)",
+                4,
+            ),
+            SearchArea::BoundingBox(_) => ("b.location && ST_MakeEnvelope($1, $2, $3, $4, 4326)", 5),
+        };
+
+        // Cities are grouped by normalized name and shown in their most common
+        // spelling, like the autocomplete terms
+        let sql = format!(
             r#"
-            SELECT 
-                id,
-                osm_id,
-                name,
-                name_en,
-                address,
-                ST_Y(location) as latitude,
-                ST_X(location) as longitude,
-                categories as "categories!: Vec<BusinessCategory>",
-                specializations as "specializations!: Vec<String>",
-                created_at,
-                updated_at,
-                logo_map_url,
-                is_registered,
-                city,
-                average_reviews,
-                review_count,
-                ST_Distance(
-                    location,
-                    ST_SetSRID(ST_MakePoint($2, $1), 4326)::geography
-                ) as distance_meters
-            FROM search.businesses
-            WHERE ST_DWithin(
-                location,
-                ST_SetSRID(ST_MakePoint($2, $1), 4326)::geography,
-                $3 * 1000
+            WITH matched AS (
+                SELECT
+                    b.categories::text[] AS categories,
+                    b.specializations,
+                    b.city,
+                    {categories} AS category_match
+                FROM search.businesses b
+                WHERE {spatial}
+                AND {attributes}
+            )
+            SELECT 'category' AS facet, value, count(*) AS count
+            FROM matched
+            CROSS JOIN LATERAL unnest(matched.categories) AS value
+            GROUP BY value
+            UNION ALL
+            SELECT 'specialization', value, count(*)
+            FROM matched
+            CROSS JOIN LATERAL unnest(matched.specializations) AS value
+            WHERE category_match
+            GROUP BY value
+            UNION ALL
+            SELECT 'city', mode() WITHIN GROUP (ORDER BY btrim(city)), count(*)
+            FROM matched
+            WHERE category_match
+            AND search.normalize_name(city) <> ''
+            GROUP BY search.normalize_name(city)
+            ORDER BY facet, count DESC, value
+            "#,
+            categories = category_predicate(filter.mode, &format!("${}", first_param)),
+            spatial = spatial,
+            attributes = attribute_predicate(attributes, first_param + 1),
+        );
+
+        let mut query = sqlx::query_as::<_, FacetRow>(&sql);
+        query = match area {
+            SearchArea::Radius { location: (latitude, longitude), radius_meters } => query
+                .bind(latitude)
+                .bind(longitude)
+                .bind(radius_meters),
+            SearchArea::BoundingBox(bbox) => query
+                .bind(bbox.min_lon)
+                .bind(bbox.min_lat)
+                .bind(bbox.max_lon)
+                .bind(bbox.max_lat),
+        };
+        let rows = query
+            .bind(filter.category_names())
+            .bind(attributes.min_rating)
+            .bind(attributes.min_reviews)
+            .bind(attributes.registered_only)
+            .bind(&attributes.specializations)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut facets = SearchFacets::default();
+        for row in rows {
+            match row.facet.as_str() {
+                "category" => {
+                    if let Ok(value) = row.value.parse() {
+                        facets.categories.push(FacetCount { value, count: row.count });
+                    }
+                }
+                "specialization" => facets.specializations.push(FacetCount { value: row.value, count: row.count }),
+                _ => facets.cities.push(FacetCount { value: row.value, count: row.count }),
+            }
+        }
+
+        Ok(facets)
+    }
+
+    async fn get_business_clusters(
+        &self,
+        bbox: &BoundingBox,
+        cell_degrees: f64,
+        filter: &CategoryFilter,
+        min_cluster_size: i64,
+    ) -> Result<ClusteredBusinesses> {
+        // Businesses snapped to a grid anchored at 0,0; cells are identified by
+        // their integer column and row
+        let matched = format!(
+            r#"
+            WITH matched AS (
+                SELECT
+                    b.id,
+                    b.location,
+                    b.categories,
+                    floor(ST_X(b.location) / $5) AS cell_x,
+                    floor(ST_Y(b.location) / $5) AS cell_y
+                FROM search.businesses b
+                WHERE b.location && ST_MakeEnvelope($1, $2, $3, $4, 4326)
+                AND {}
+            )
+            "#,
+            category_predicate(filter.mode, "$6")
+        );
+
+        let clusters_sql = format!(
+            r#"
+            {matched},
+            clusters AS (
+                SELECT
+                    cell_x,
+                    cell_y,
+                    count(*) AS business_count,
+                    ST_Y(ST_Centroid(ST_Collect(location))) AS latitude,
+                    ST_X(ST_Centroid(ST_Collect(location))) AS longitude
+                FROM matched
+                GROUP BY cell_x, cell_y
+                HAVING count(*) >= $7
+            ),
+            breakdown AS (
+                SELECT cell_x, cell_y, category, count(*) AS business_count
+                FROM matched
+                CROSS JOIN unnest(matched.categories::text[]) AS category
+                GROUP BY cell_x, cell_y, category
+            )
+            SELECT
+                c.latitude,
+                c.longitude,
+                c.business_count,
+                COALESCE(
+                    jsonb_object_agg(bd.category, bd.business_count)
+                        FILTER (WHERE bd.category IS NOT NULL),
+                    '{{}}'
+                ) AS categories
+            FROM clusters c
+            LEFT JOIN breakdown bd USING (cell_x, cell_y)
+            GROUP BY c.cell_x, c.cell_y, c.latitude, c.longitude, c.business_count
+            ORDER BY c.business_count DESC
+            "#
+        );
+
+        let businesses_sql = format!(
+            r#"
+            {matched}
+            SELECT {BUSINESS_COLUMNS}
+            FROM matched m
+            JOIN search.businesses b ON b.id = m.id
+            WHERE (m.cell_x, m.cell_y) IN (
+                SELECT cell_x, cell_y
+                FROM matched
+                GROUP BY cell_x, cell_y
+                HAVING count(*) < $7
             )
-            AND $4 = ANY(categories::text[])
-            ORDER BY ST_Distance(
-                location,
-                ST_SetSRID(ST_MakePoint($2, $1), 4326)::geography
+            ORDER BY b.id
+            "#
+        );
+
+        let cluster_rows: Vec<ClusterRow> = sqlx::query_as(&clusters_sql)
+            .bind(bbox.min_lon)
+            .bind(bbox.min_lat)
+            .bind(bbox.max_lon)
+            .bind(bbox.max_lat)
+            .bind(cell_degrees)
+            .bind(filter.category_names())
+            .bind(min_cluster_size)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let business_rows: Vec<BusinessRow> = sqlx::query_as(&businesses_sql)
+            .bind(bbox.min_lon)
+            .bind(bbox.min_lat)
+            .bind(bbox.max_lon)
+            .bind(bbox.max_lat)
+            .bind(cell_degrees)
+            .bind(filter.category_names())
+            .bind(min_cluster_size)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(ClusteredBusinesses {
+            clusters: cluster_rows.into_iter().map(BusinessCluster::from).collect(),
+            businesses: business_rows.into_iter().map(Business::from).collect(),
+            ..Default::default()
+        })
+    }
+
+    async fn get_business_tile(
+        &self,
+        tile: &TileCoordinates,
+        filter: &CategoryFilter,
+    ) -> Result<Vec<u8>> {
+        // The tile envelope is in web mercator; comparing against it transformed
+        // back to 4326 keeps the GIST index on location usable. The primary
+        // category prefers tag-derived categories, then PRIMARY_CATEGORY_ORDER.
+        let sql = format!(
+            r#"
+            WITH bounds AS (
+                SELECT ST_TileEnvelope($1, $2, $3) AS geom
+            ),
+            features AS (
+                SELECT
+                    ST_AsMVTGeom(ST_Transform(b.location, 3857), bounds.geom) AS geom,
+                    b.id::text AS id,
+                    b.name,
+                    (
+                        SELECT c
+                        FROM unnest(b.categories::text[]) AS c
+                        ORDER BY c = ANY(b.inferred_categories::text[]), array_position($6::text[], c)
+                        LIMIT 1
+                    ) AS category,
+                    COALESCE(b.is_registered, FALSE) AS "isRegistered",
+                    b.average_reviews::float8 AS rating,
+                    b.review_count AS "reviewCount"
+                FROM search.businesses b
+                CROSS JOIN bounds
+                WHERE b.location && ST_Transform(bounds.geom, 4326)
+                AND {}
             )
+            SELECT ST_AsMVT(features, $5, 4096, 'geom')
+            FROM features
+            "#,
+            category_predicate(filter.mode, "$4")
+        );
+
+        let tile: Option<Vec<u8>> = sqlx::query_scalar(&sql)
+            .bind(tile.z as i32)
+            .bind(tile.x as i32)
+            .bind(tile.y as i32)
+            .bind(filter.category_names())
+            .bind(BUSINESS_TILE_LAYER)
+            .bind(PRIMARY_CATEGORY_ORDER.iter().map(|c| c.to_string()).collect::<Vec<_>>())
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(tile.unwrap_or_default())
+    }
+
+    async fn get_businesses_along_route(&self, query: &RouteSearchQuery) -> Result<Vec<Business>> {
+        let route = serde_json::to_string(&Geometry::LineString {
+            coordinates: query.route.clone(),
+        })
+        .map_err(|e| AppError::validation("route", e.to_string()))?;
+
+        // The planar ST_DWithin uses the location index to prefilter, the
+        // geography one measures the corridor exactly. The offset along the
+        // route is the geodesic length of the route up to the business's
+        // nearest point; scaling the planar fraction would mix degree lengths.
+        let sql = format!(
+            r#"
+            WITH route AS (
+                SELECT ST_SetSRID(ST_GeomFromGeoJSON($1), 4326) AS geom
+            )
+            SELECT
+                {},
+                ST_Distance(b.location::geography, route.geom::geography) AS distance_from_route_meters,
+                ST_Length(ST_LineSubstring(
+                    route.geom, 0, ST_LineLocatePoint(route.geom, b.location)
+                )::geography) AS along_route_meters
+            FROM search.businesses b
+            CROSS JOIN route
+            WHERE ST_DWithin(b.location, route.geom, $3)
+            AND ST_DWithin(b.location::geography, route.geom::geography, $2)
+            AND {}
+            ORDER BY along_route_meters, b.id
             LIMIT $5
             "#,
-            lat,
-            lon,
-            radius_km,
-            category_str,
-            limit
+            BUSINESS_COLUMNS,
+            category_predicate(query.filter.mode, "$4")
+        );
+
+        let rows: Vec<RouteBusinessRow> = sqlx::query_as(&sql)
+            .bind(route)
+            .bind(query.corridor_meters)
+            .bind(query.corridor_degrees())
+            .bind(query.filter.category_names())
+            .bind(query.limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Business::from).collect())
+    }
+
+    async fn get_nearest_businesses(&self, query: &NearestSearchQuery) -> Result<Vec<Business>> {
+        let (latitude, longitude) = query.location;
+
+        // `<->` walks the GIST index in planar degree distance, which drifts from
+        // true distance away from the equator, so the inner scan overfetches and
+        // the outer query reorders by geodesic distance.
+        let sql = format!(
+            r#"
+            SELECT *
+            FROM (
+                SELECT {columns}, {proximity}
+                FROM search.businesses b
+                WHERE {categories}
+                AND ($4::float8 IS NULL OR ST_DWithin(
+                    b.location::geography,
+                    ST_SetSRID(ST_MakePoint($2, $1), 4326)::geography,
+                    $4
+                ))
+                ORDER BY b.location <-> ST_SetSRID(ST_MakePoint($2, $1), 4326)
+                LIMIT $5 * {overfetch}
+            ) nearest
+            ORDER BY nearest.distance_meters, nearest.id
+            LIMIT $5
+            "#,
+            columns = BUSINESS_COLUMNS,
+            proximity = proximity_columns("$1", "$2"),
+            categories = category_predicate(query.filter.mode, "$3"),
+            overfetch = KNN_OVERFETCH,
+        );
+
+        let rows: Vec<DistancedBusinessRow> = sqlx::query_as(&sql)
+            .bind(latitude)
+            .bind(longitude)
+            .bind(query.filter.category_names())
+            .bind(query.max_distance_meters)
+            .bind(query.limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Business::from).collect())
+    }
+
+    async fn get_nearest_businesses_by_category(
+        &self,
+        query: &NearestByCategoryQuery,
+    ) -> Result<Vec<CategoryNearest>> {
+        let (latitude, longitude) = query.location;
+        let categories: Vec<String> = query.categories.iter().map(|c| c.to_string()).collect();
+
+        // One index-driven nearest-neighbour scan per category, reordered by
+        // geodesic distance like `get_nearest_businesses`
+        let sql = format!(
+            r#"
+            SELECT requested.category, nearest.*
+            FROM unnest($3::text[]) AS requested(category)
+            CROSS JOIN LATERAL (
+                SELECT *
+                FROM (
+                    SELECT {columns}, {proximity}
+                    FROM search.businesses b
+                    WHERE b.categories @> ARRAY[requested.category]::search.business_category[]
+                    AND ($4::float8 IS NULL OR ST_DWithin(
+                        b.location::geography,
+                        ST_SetSRID(ST_MakePoint($2, $1), 4326)::geography,
+                        $4
+                    ))
+                    ORDER BY b.location <-> ST_SetSRID(ST_MakePoint($2, $1), 4326)
+                    LIMIT $5 * {overfetch}
+                ) candidates
+                ORDER BY candidates.distance_meters, candidates.id
+                LIMIT $5
+            ) nearest
+            "#,
+            columns = BUSINESS_COLUMNS,
+            proximity = proximity_columns("$1", "$2"),
+            overfetch = KNN_OVERFETCH,
+        );
+
+        let rows: Vec<CategoryBusinessRow> = sqlx::query_as(&sql)
+            .bind(latitude)
+            .bind(longitude)
+            .bind(&categories)
+            .bind(query.max_distance_meters)
+            .bind(query.per_category)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut grouped: Vec<CategoryNearest> = query.categories
+            .iter()
+            .map(|category| CategoryNearest { category: category.clone(), businesses: Vec::new() })
+            .collect();
+        for row in rows {
+            if let Some(group) = grouped.iter_mut().find(|g| g.category.to_string() == row.category) {
+                group.businesses.push(Business::from(row.business));
+            }
+        }
+
+        Ok(grouped)
+    }
+
+    async fn get_area_sq_km(&self, area: &Geometry) -> Result<f64> {
+        let area = serde_json::to_string(area)
+            .map_err(|e| AppError::validation("geometry", e.to_string()))?;
+
+        let sq_km: f64 = sqlx::query_scalar(
+            r#"
+            SELECT ST_Area(
+                ST_MakeValid(ST_SetSRID(ST_GeomFromGeoJSON($1), 4326))::geography
+            ) / 1000000.0
+            "#,
+        )
+        .bind(area)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(sq_km)
+    }
+
+    async fn get_businesses_in_area(&self, query: &AreaSearchQuery) -> Result<Vec<Business>> {
+        let area = serde_json::to_string(&query.area)
+            .map_err(|e| AppError::validation("geometry", e.to_string()))?;
+
+        // Hand-drawn polygons are often self-intersecting; ST_MakeValid repairs
+        // them instead of failing the search
+        let sql = format!(
+            r#"
+            WITH area AS (
+                SELECT ST_MakeValid(ST_SetSRID(ST_GeomFromGeoJSON($1), 4326)) AS geom
+            )
+            SELECT {}
+            FROM search.businesses b
+            CROSS JOIN area
+            WHERE ST_Covers(area.geom, b.location)
+            AND {}
+            ORDER BY b.is_registered IS TRUE DESC, b.review_count DESC, b.id
+            LIMIT $3
+            "#,
+            BUSINESS_COLUMNS,
+            category_predicate(query.filter.mode, "$2")
+        );
+
+        let rows: Vec<BusinessRow> = sqlx::query_as(&sql)
+            .bind(area)
+            .bind(query.filter.category_names())
+            .bind(query.limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Business::from).collect())
+    }
+
+    async fn search_businesses_by_text(&self, query: &TextSearchQuery) -> Result<Vec<Business>> {
+        let (latitude, longitude) = query.location.unzip();
+
+        // Typo tolerance comes from trigram word similarity (`<%`), exact words
+        // from full-text search; both run on the transliterated document so
+        // Cyrillic and Latin spellings meet.
+        let sql = format!(
+            r#"
+            WITH query AS (
+                SELECT
+                    search.normalize_name($1) AS text,
+                    plainto_tsquery('simple', search.normalize_name($1)) AS tsquery
+            )
+            SELECT {columns}, {proximity}
+            FROM search.businesses b
+            CROSS JOIN query
+            WHERE (
+                to_tsvector('simple', {document}) @@ query.tsquery
+                OR query.text <% {document}
+            )
+            AND {categories}
+            ORDER BY (
+                word_similarity(query.text, {document})
+                + ts_rank(to_tsvector('simple', {document}), query.tsquery)
+            ) * (
+                $5 + $6 * COALESCE(exp(-ST_Distance(
+                    b.location::geography,
+                    ST_SetSRID(ST_MakePoint($3, $2), 4326)::geography
+                ) / $7), 1.0)
+            ) DESC, b.id
+            LIMIT $8
+            "#,
+            columns = BUSINESS_COLUMNS,
+            proximity = proximity_columns("$2", "$3"),
+            document = SEARCH_DOCUMENT,
+            categories = category_predicate(query.filter.mode, "$4"),
+        );
+
+        let rows: Vec<DistancedBusinessRow> = sqlx::query_as(&sql)
+            .bind(&query.text)
+            .bind(latitude)
+            .bind(longitude)
+            .bind(query.filter.category_names())
+            .bind(TEXT_RELEVANCE_WEIGHT)
+            .bind(1.0 - TEXT_RELEVANCE_WEIGHT)
+            .bind(DISTANCE_DECAY_METERS)
+            .bind(query.limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Business::from).collect())
+    }
+
+    async fn autocomplete(
+        &self,
+        text: &str,
+        location: Option<(f64, f64)>,
+        limit: i64,
+    ) -> Result<Vec<AutocompleteSuggestion>> {
+        let (latitude, longitude) = location.unzip();
+
+        // Normalized up front so the prefix reaches the planner as a bound
+        // parameter; a pattern computed inside the query can't use the btree
+        let prefix: String = sqlx::query_scalar("SELECT search.normalize_name($1)")
+            .bind(text)
+            .fetch_one(&self.pool)
+            .await?;
+        let Some(upper) = prefix_upper_bound(&prefix) else {
+            return Ok(Vec::new());
+        };
+        // Normalized text is [a-z0-9 ] only, so it holds no LIKE wildcards
+        let word_pattern = format!("% {}%", prefix);
+
+        // Name prefixes are a range scan on the `text_pattern_ops` btree, word
+        // prefixes inside names use the trigram index; cities and
+        // specializations come from the small autocomplete_terms view
+        let rows: Vec<AutocompleteRow> = sqlx::query_as(
+            r#"
+            (
+                SELECT
+                    'business' AS kind,
+                    b.id,
+                    b.name AS text,
+                    b.city,
+                    NULL::bigint AS business_count
+                FROM (
+                    SELECT id, MIN(rank) AS rank
+                    FROM (
+                        SELECT id, 0 AS rank
+                        FROM search.businesses
+                        WHERE search.normalize_name(name) ~>=~ $1
+                          AND search.normalize_name(name) ~<~ $2
+                        UNION ALL
+                        SELECT id, 1 AS rank
+                        FROM search.businesses
+                        WHERE search.normalize_name(name) LIKE $3
+                    ) matches
+                    GROUP BY id
+                ) m
+                JOIN search.businesses b ON b.id = m.id
+                ORDER BY
+                    m.rank,
+                    b.location <-> ST_SetSRID(ST_MakePoint($5, $4), 4326),
+                    length(b.name)
+                LIMIT $6
+            )
+            UNION ALL
+            (
+                SELECT
+                    t.kind,
+                    NULL::uuid AS id,
+                    t.term AS text,
+                    NULL AS city,
+                    t.business_count
+                FROM search.autocomplete_terms t
+                WHERE t.normalized ~>=~ $1
+                  AND t.normalized ~<~ $2
+                ORDER BY t.business_count DESC
+                LIMIT $6
+            )
+            "#,
         )
+        .bind(&prefix)
+        .bind(upper)
+        .bind(word_pattern)
+        .bind(latitude)
+        .bind(longitude)
+        .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(businesses.into_iter().map(|row| Business {
-            id: row.id,
-            osm_id: row.osm_id,
-            name: row.name,
-            name_en: row.name_en,
-            address: row.address,
-            latitude: row.latitude.unwrap_or(0.0),
-            longitude: row.longitude.unwrap_or(0.0),
-            categories: row.categories,
-            specializations: Some(row.specializations),
-            created_at: row.created_at.expect("created_at should never be null"),
-            updated_at: row.updated_at.expect("updated_at should never be null"),
-            logo_map_url: row.logo_map_url,
-            is_registered: row.is_registered.unwrap_or(false),
-            city: row.city,
-            average_reviews: row.average_reviews.to_f64().unwrap_or(0.0),
-            review_count: row.review_count,
-        }).collect())
+        Ok(rows.into_iter().filter_map(AutocompleteRow::into_suggestion).collect())
+    }
+
+    async fn refresh_autocomplete_terms(&self) -> Result<()> {
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY search.autocomplete_terms")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn refresh_cities(&self) -> Result<()> {
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY search.cities")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_cities(&self) -> Result<Vec<City>> {
+        let sql = format!(
+            "SELECT {} FROM search.cities ORDER BY business_count DESC, slug",
+            CITY_COLUMNS
+        );
+
+        let rows: Vec<CityRow> = sqlx::query_as(&sql)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(City::from).collect())
+    }
+
+    async fn get_city(&self, slug: &str) -> Result<Option<City>> {
+        let sql = format!("SELECT {} FROM search.cities WHERE slug = $1", CITY_COLUMNS);
+
+        let row: Option<CityRow> = sqlx::query_as(&sql)
+            .bind(slug)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(City::from))
+    }
+
+    async fn get_businesses_in_city(
+        &self,
+        slug: &str,
+        filter: &CategoryFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Business>> {
+        let sql = format!(
+            r#"
+            SELECT {}
+            FROM search.businesses b
+            WHERE search.city_slug(b.city) = $1
+            AND {}
+            ORDER BY b.is_registered IS TRUE DESC, b.review_count DESC, b.id
+            LIMIT $3 OFFSET $4
+            "#,
+            BUSINESS_COLUMNS,
+            category_predicate(filter.mode, "$2")
+        );
+
+        let rows: Vec<BusinessRow> = sqlx::query_as(&sql)
+            .bind(slug)
+            .bind(filter.category_names())
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Business::from).collect())
+    }
+
+    async fn detect_duplicates(
+        &self,
+        max_distance_meters: f64,
+        min_name_similarity: f32,
+    ) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM search.business_duplicate_candidates")
+            .execute(&mut *tx)
+            .await?;
+
+        // The planar ST_DWithin narrows candidates through the GIST index
+        // (degrees widened by latitude), the geography one checks real metres.
+        let result = sqlx::query(
+            r#"
+            INSERT INTO search.business_duplicate_candidates (
+                business_id, duplicate_id, distance_meters, name_similarity, shared_categories
+            )
+            SELECT
+                a.id,
+                b.id,
+                ST_Distance(a.location::geography, b.location::geography),
+                similarity(search.normalize_name(a.name), search.normalize_name(b.name)),
+                ARRAY(SELECT unnest(a.categories) INTERSECT SELECT unnest(b.categories))
+            FROM search.businesses a
+            JOIN search.businesses b
+                ON a.id < b.id
+                AND ST_DWithin(a.location, b.location, $1 / 111320.0 / cos(radians(ST_Y(a.location))))
+                AND ST_DWithin(a.location::geography, b.location::geography, $1)
+                AND a.categories && b.categories
+            WHERE a.name IS NOT NULL
+              AND b.name IS NOT NULL
+              AND similarity(search.normalize_name(a.name), search.normalize_name(b.name)) >= $2
+            "#,
+        )
+        .bind(max_distance_meters)
+        .bind(min_name_similarity)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn get_duplicate_candidates(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<DuplicateCandidate>> {
+        let rows: Vec<DuplicateCandidateRow> = sqlx::query_as(
+            r#"
+            SELECT
+                c.business_id,
+                a.osm_id AS business_osm_id,
+                a.name AS business_name,
+                COALESCE(a.is_registered, FALSE) AS business_is_registered,
+                c.duplicate_id,
+                b.osm_id AS duplicate_osm_id,
+                b.name AS duplicate_name,
+                COALESCE(b.is_registered, FALSE) AS duplicate_is_registered,
+                c.distance_meters,
+                c.name_similarity,
+                c.shared_categories::text[] AS shared_categories,
+                c.detected_at
+            FROM search.business_duplicate_candidates c
+            JOIN search.businesses a ON a.id = c.business_id
+            JOIN search.businesses b ON b.id = c.duplicate_id
+            ORDER BY c.name_similarity DESC, c.distance_meters
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(DuplicateCandidate::from).collect())
+    }
+
+    async fn merge_businesses(&self, keep_id: Uuid, retire_id: Uuid) -> Result<Business> {
+        if keep_id == retire_id {
+            return Err(AppError::BadRequest("Cannot merge a business into itself".to_string()));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let rows: Vec<(Uuid, Option<i64>, Option<bool>)> = sqlx::query_as(
+            "SELECT id, osm_id, is_registered FROM search.businesses WHERE id = ANY($1) FOR UPDATE",
+        )
+        .bind(vec![keep_id, retire_id])
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let find = |id: Uuid| {
+            rows.iter()
+                .find(|(row_id, _, _)| *row_id == id)
+                .cloned()
+                .ok_or_else(|| AppError::BadRequest(format!("Business {} does not exist", id)))
+        };
+        let (_, keep_osm_id, keep_registered) = find(keep_id)?;
+        let (_, retire_osm_id, retire_registered) = find(retire_id)?;
+
+        if retire_registered.unwrap_or(false) && !keep_registered.unwrap_or(false) {
+            return Err(AppError::Conflict(format!(
+                "Registered business {} can't be merged into unregistered {}",
+                retire_id, keep_id
+            )));
+        }
+
+        // Free the retired osm_id before the kept row possibly takes it over
+        sqlx::query("UPDATE search.businesses SET osm_id = NULL WHERE id = $1")
+            .bind(retire_id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE search.businesses AS k SET
+                categories = ARRAY(
+                    SELECT DISTINCT c FROM unnest(k.categories || r.categories) AS c ORDER BY c
+                ),
+                merged_categories = ARRAY(
+                    SELECT DISTINCT c
+                    FROM unnest(k.merged_categories || r.categories || r.merged_categories) AS c
+                    ORDER BY c
+                ),
+                specializations = ARRAY(
+                    SELECT DISTINCT s
+                    FROM unnest(COALESCE(k.specializations, '{}') || COALESCE(r.specializations, '{}')) AS s
+                    ORDER BY s
+                ),
+                osm_id = COALESCE(k.osm_id, $3),
+                name = COALESCE(k.name, r.name),
+                name_en = COALESCE(k.name_en, r.name_en),
+                address = COALESCE(k.address, r.address),
+                city = COALESCE(k.city, r.city),
+                logo_map_url = COALESCE(k.logo_map_url, r.logo_map_url)
+            FROM search.businesses AS r
+            WHERE k.id = $1 AND r.id = $2
+            "#,
+        )
+        .bind(keep_id)
+        .bind(retire_id)
+        .bind(retire_osm_id)
+        .execute(&mut *tx)
+        .await?;
+
+        // Only remember the retired OSM id when the kept row couldn't take it over
+        let retired_osm_id = keep_osm_id.and(retire_osm_id);
+        Self::retire_business(&mut tx, retire_id, keep_id, retired_osm_id).await?;
+
+        tx.commit().await?;
+
+        tracing::info!("Merged business {} into {}", retire_id, keep_id);
+
+        self.get_business_by_id(keep_id)
+            .await?
+            .ok_or_else(|| sqlx::Error::RowNotFound.into())
+    }
+
+    async fn get_data_quality_report(
+        &self,
+        criteria: &DataQualityCriteria,
+        limit: i64,
+        offset: i64,
+    ) -> Result<DataQualityReport> {
+        let issue_counts: Vec<String> = DataQualityIssue::iter()
+            .map(|issue| format!("COUNT(*) FILTER (WHERE {}) AS {}", issue_predicate(issue), issue))
+            .collect();
+        let issue_counts = issue_counts.join(",\n                ");
+
+        let summary_sql = format!(
+            r#"
+            WITH {}
+            SELECT
+                COUNT(*) AS total,
+                (
+                    SELECT COUNT(*)
+                    FROM (
+                        SELECT DISTINCT c.category, g.city
+                        FROM search.businesses g
+                        CROSS JOIN LATERAL unnest(g.categories) AS c(category)
+                    ) groups
+                ) AS group_count,
+                {}
+            FROM search.businesses b
+            CROSS JOIN country
+            "#,
+            COUNTRY_CTE,
+            issue_counts
+        );
+
+        let summary_row = sqlx::query(&summary_sql)
+            .bind(&criteria.country_polygon_wkt)
+            .bind(criteria.stale_after_days)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let groups_sql = format!(
+            r#"
+            WITH {}
+            SELECT
+                c.category::text AS category,
+                b.city,
+                COUNT(*) AS total,
+                {}
+            FROM search.businesses b
+            CROSS JOIN country
+            CROSS JOIN LATERAL unnest(b.categories) AS c(category)
+            GROUP BY c.category, b.city
+            ORDER BY c.category, total DESC, b.city NULLS LAST
+            LIMIT $3 OFFSET $4
+            "#,
+            COUNTRY_CTE,
+            issue_counts
+        );
+
+        let group_rows = sqlx::query(&groups_sql)
+            .bind(&criteria.country_polygon_wkt)
+            .bind(criteria.stale_after_days)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut groups = Vec::with_capacity(group_rows.len());
+        for row in &group_rows {
+            let category: String = row.try_get("category")?;
+            // Stored categories are always variants; skip any the code doesn't know yet
+            let Ok(category) = category.parse() else {
+                continue;
+            };
+            groups.push(DataQualityGroup {
+                category,
+                city: row.try_get("city")?,
+                counts: data_quality_counts(row)?,
+            });
+        }
+
+        Ok(DataQualityReport {
+            stale_after_days: criteria.stale_after_days,
+            summary: data_quality_counts(&summary_row)?,
+            group_count: summary_row.try_get("group_count")?,
+            groups,
+        })
+    }
+
+    async fn get_businesses_with_issue(
+        &self,
+        issue: DataQualityIssue,
+        criteria: &DataQualityCriteria,
+        category: Option<&BusinessCategory>,
+        city: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Business>> {
+        let sql = format!(
+            r#"
+            WITH {}
+            SELECT {}
+            FROM search.businesses b
+            CROSS JOIN country
+            WHERE {}
+              AND ($3::text IS NULL OR $3 = ANY(b.categories::text[]))
+              AND ($4::text IS NULL OR b.city = $4)
+            ORDER BY b.updated_at, b.id
+            LIMIT $5 OFFSET $6
+            "#,
+            COUNTRY_CTE,
+            BUSINESS_COLUMNS,
+            issue_predicate(issue)
+        );
+
+        let rows: Vec<BusinessRow> = sqlx::query_as(&sql)
+            .bind(&criteria.country_polygon_wkt)
+            .bind(criteria.stale_after_days)
+            .bind(category.map(|c| c.to_string()))
+            .bind(city)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Business::from).collect())
     }
 }
 
 impl PostgresBusinessRepository {
-    async fn upsert_business(&self, business: &BusinessInsert) -> Result<Uuid> {
-        let result = sqlx::query!(
+    /// Upserts an OSM business. Returns `None` when the OSM id is owned by a
+    /// registered business, which the sync must leave untouched.
+    /// Categories merged in from duplicates are kept on top of the OSM ones.
+    async fn upsert_business(&self, business: &BusinessInsert) -> Result<Option<Uuid>> {
+        let categories: Vec<String> = business.categories.iter().map(|c| c.to_string()).collect();
+        let inferred_categories: Vec<String> = business.inferred_categories
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+
+        let id = sqlx::query_scalar(
             r#"
             INSERT INTO search.businesses (
                 osm_id, name, name_en, address, location,
-                categories, city
+                categories, inferred_categories, city,
+                phone, email, website, opening_hours
             )
             VALUES (
                 $1, $2, $3, $4, ST_SetSRID(ST_MakePoint($5, $6), 4326),
-                $7::search.business_category[], $8
+                $7::text[]::search.business_category[],
+                $8::text[]::search.business_category[], $9,
+                $10, $11, $12, $13
             )
             ON CONFLICT (osm_id)
             DO UPDATE SET
-                name            = EXCLUDED.name,
-                name_en         = EXCLUDED.name_en,
-                address         = EXCLUDED.address,
-                location        = EXCLUDED.location,
-                categories      = EXCLUDED.categories,
-                updated_at      = NOW(),
-                city            = EXCLUDED.city
+                name                = EXCLUDED.name,
+                name_en             = EXCLUDED.name_en,
+                address             = EXCLUDED.address,
+                location            = EXCLUDED.location,
+                categories          = ARRAY(
+                    SELECT DISTINCT c
+                    FROM unnest(EXCLUDED.categories || search.businesses.merged_categories) AS c
+                    ORDER BY c
+                ),
+                inferred_categories = EXCLUDED.inferred_categories,
+                updated_at          = NOW(),
+                city                = EXCLUDED.city,
+                phone               = EXCLUDED.phone,
+                email               = EXCLUDED.email,
+                website             = EXCLUDED.website,
+                opening_hours       = EXCLUDED.opening_hours
             WHERE search.businesses.is_registered = FALSE
             RETURNING id
             "#,
-            business.osm_id,
-            business.name,
-            business.name_en,
-            business.address,
-            business.longitude,
-            business.latitude,
-            &business.categories as &[BusinessCategory],
-            business.city,
         )
-        .fetch_one(&self.pool)
+        .bind(business.osm_id)
+        .bind(&business.name)
+        .bind(&business.name_en)
+        .bind(&business.address)
+        .bind(business.longitude)
+        .bind(business.latitude)
+        .bind(categories)
+        .bind(inferred_categories)
+        .bind(&business.city)
+        .bind(&business.phone)
+        .bind(&business.email)
+        .bind(&business.website)
+        .bind(&business.opening_hours)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// Resolves the OSM identity claimed by a registration and releases it from
+    /// the unregistered row currently holding it, so the registered row can take
+    /// it over and the next Overpass sync matches it instead of re-inserting.
+    /// Returns the claimed OSM id and the row that must be retired once the
+    /// registered business has been written.
+    async fn claim_osm_identity(
+        tx: &mut Transaction<'_, Postgres>,
+        id: Uuid,
+        claim_business_id: Option<Uuid>,
+        claim_osm_id: Option<i64>,
+    ) -> Result<(Option<i64>, Option<Uuid>)> {
+        let claimed = match (claim_business_id, claim_osm_id) {
+            (Some(business_id), _) => {
+                let row = Self::lock_claimed_business(tx, "id = COALESCE(
+                        (SELECT business_id FROM search.business_redirects WHERE retired_id = $1),
+                        $1::uuid
+                    )", business_id).await?;
+
+                if row.is_none() {
+                    return Err(AppError::BadRequest(format!(
+                        "Claimed business {} does not exist",
+                        business_id
+                    )));
+                }
+                row
+            }
+            (None, Some(osm_id)) => {
+                let row = Self::lock_claimed_business(tx, "osm_id = $1", osm_id).await?;
+
+                // Not imported yet: the next sync will find the registered row by osm_id
+                if row.is_none() {
+                    return Ok((Some(osm_id), None));
+                }
+                row
+            }
+            (None, None) => None,
+        };
+
+        let Some((claimed_id, osm_id, is_registered)) = claimed else {
+            return Ok((None, None));
+        };
+
+        if claimed_id == id {
+            return Ok((osm_id, None));
+        }
+
+        if is_registered.unwrap_or(false) {
+            return Err(AppError::Conflict(format!(
+                "Business {} is already registered",
+                claimed_id
+            )));
+        }
+
+        // Free the unique osm_id so the registered row can take it
+        sqlx::query("UPDATE search.businesses SET osm_id = NULL WHERE id = $1")
+            .bind(claimed_id)
+            .execute(&mut **tx)
+            .await?;
+
+        tracing::info!(
+            "Business {} claimed OSM business {} (osm_id {:?})",
+            id, claimed_id, osm_id
+        );
+
+        Ok((osm_id, Some(claimed_id)))
+    }
+
+    /// Locks the business a registration claims, so concurrent claims of the
+    /// same business or OSM id queue up instead of both passing the ownership
+    /// check. A claim that waited for the lock may find the row retired by the
+    /// claim ahead of it; the lookup is then repeated with a fresh snapshot,
+    /// which sees the new owner through its redirect or osm_id.
+    async fn lock_claimed_business<T>(
+        tx: &mut Transaction<'_, Postgres>,
+        predicate: &str,
+        value: T,
+    ) -> Result<Option<(Uuid, Option<i64>, Option<bool>)>>
+    where
+        T: for<'q> sqlx::Encode<'q, Postgres> + sqlx::Type<Postgres> + Copy + Send,
+    {
+        let sql = format!(
+            "SELECT id, osm_id, is_registered FROM search.businesses WHERE {} FOR UPDATE",
+            predicate
+        );
+
+        for _ in 0..2 {
+            let row = sqlx::query_as(&sql)
+                .bind(value)
+                .fetch_optional(&mut **tx)
+                .await?;
+            if row.is_some() {
+                return Ok(row);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Deletes `retired_id` and redirects it, and anything that already redirected
+    /// to it, to `business_id`.
+    async fn retire_business(
+        tx: &mut Transaction<'_, Postgres>,
+        retired_id: Uuid,
+        business_id: Uuid,
+        retired_osm_id: Option<i64>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE search.business_redirects SET business_id = $2 WHERE business_id = $1")
+            .bind(retired_id)
+            .bind(business_id)
+            .execute(&mut **tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO search.business_redirects (retired_id, business_id, retired_osm_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (retired_id) DO UPDATE SET
+                business_id = EXCLUDED.business_id,
+                retired_osm_id = EXCLUDED.retired_osm_id
+            "#,
+        )
+        .bind(retired_id)
+        .bind(business_id)
+        .bind(retired_osm_id)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query("DELETE FROM search.businesses WHERE id = $1")
+            .bind(retired_id)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Follows the redirect of a retired business id, if there is one.
+    async fn resolve_business_id(&self, id: Uuid) -> Result<Uuid> {
+        let target: Option<Uuid> = sqlx::query_scalar(
+            "SELECT business_id FROM search.business_redirects WHERE retired_id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
         .await?;
 
-        Ok(result.id)
+        Ok(target.unwrap_or(id))
     }
-}
\ No newline at end of file
+
+    async fn get_retired_osm_ids(&self) -> Result<HashSet<i64>> {
+        let ids: Vec<i64> = sqlx::query_scalar(
+            "SELECT retired_osm_id FROM search.business_redirects WHERE retired_osm_id IS NOT NULL",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids.into_iter().collect())
+    }
+}
+
+#[derive(FromRow)]
+struct DuplicateCandidateRow {
+    business_id: Uuid,
+    business_osm_id: Option<i64>,
+    business_name: Option<String>,
+    business_is_registered: bool,
+    duplicate_id: Uuid,
+    duplicate_osm_id: Option<i64>,
+    duplicate_name: Option<String>,
+    duplicate_is_registered: bool,
+    distance_meters: f64,
+    name_similarity: f32,
+    shared_categories: Vec<String>,
+    detected_at: DateTime<Utc>,
+}
+
+impl From<DuplicateCandidateRow> for DuplicateCandidate {
+    fn from(row: DuplicateCandidateRow) -> Self {
+        Self {
+            business: DuplicateSide {
+                id: row.business_id,
+                osm_id: row.business_osm_id,
+                name: row.business_name,
+                is_registered: row.business_is_registered,
+            },
+            duplicate: DuplicateSide {
+                id: row.duplicate_id,
+                osm_id: row.duplicate_osm_id,
+                name: row.duplicate_name,
+                is_registered: row.duplicate_is_registered,
+            },
+            distance_meters: row.distance_meters,
+            name_similarity: row.name_similarity,
+            shared_categories: row.shared_categories
+                .iter()
+                .filter_map(|c| c.parse().ok())
+                .collect(),
+            detected_at: row.detected_at,
+        }
+    }
+}
+
+/// Columns selected into a `BusinessRow`, with the businesses table aliased as `b`
+const BUSINESS_COLUMNS: &str = r#"
+    b.id,
+    b.osm_id,
+    b.name,
+    b.name_en,
+    b.address,
+    ST_Y(b.location) AS latitude,
+    ST_X(b.location) AS longitude,
+    b.categories::text[] AS categories,
+    b.specializations,
+    COALESCE(b.created_at, NOW()) AS created_at,
+    COALESCE(b.updated_at, NOW()) AS updated_at,
+    b.logo_map_url,
+    COALESCE(b.is_registered, FALSE) AS is_registered,
+    b.city,
+    b.average_reviews::float8 AS average_reviews,
+    b.review_count
+"#;
+
+#[derive(FromRow)]
+struct SortedBusinessRow {
+    #[sqlx(flatten)]
+    business: DistancedBusinessRow,
+    sort_key: String,
+}
+
+/// A business with `proximity_columns`, which are NULL when the search had no point
+#[derive(FromRow)]
+struct DistancedBusinessRow {
+    #[sqlx(flatten)]
+    business: BusinessRow,
+    distance_meters: Option<f64>,
+    bearing_degrees: Option<f64>,
+}
+
+impl From<DistancedBusinessRow> for Business {
+    fn from(row: DistancedBusinessRow) -> Self {
+        let proximity = row.distance_meters.map(|distance_meters| Proximity {
+            distance_meters,
+            bearing_degrees: row.bearing_degrees.unwrap_or(0.0),
+        });
+
+        Self {
+            proximity,
+            ..Business::from(row.business)
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct FacetRow {
+    facet: String,
+    value: String,
+    count: i64,
+}
+
+/// A business with its details and the id it was requested by, which differs
+/// from its own for a retired id
+#[derive(FromRow)]
+struct BusinessDetailsRow {
+    requested_id: Uuid,
+    #[sqlx(flatten)]
+    business: BusinessRow,
+    phone: Option<String>,
+    email: Option<String>,
+    website: Option<String>,
+    opening_hours: Option<String>,
+    inferred_categories: Vec<String>,
+}
+
+impl From<BusinessDetailsRow> for BusinessDetails {
+    fn from(row: BusinessDetailsRow) -> Self {
+        Self {
+            business: Business::from(row.business),
+            phone: row.phone,
+            email: row.email,
+            website: row.website,
+            opening_hours: row.opening_hours,
+            inferred_categories: row.inferred_categories
+                .iter()
+                .filter_map(|c| c.parse().ok())
+                .collect(),
+        }
+    }
+}
+
+/// A nearest business together with the requested category it was found for
+#[derive(FromRow)]
+struct CategoryBusinessRow {
+    category: String,
+    #[sqlx(flatten)]
+    business: DistancedBusinessRow,
+}
+
+#[derive(FromRow)]
+struct RouteBusinessRow {
+    #[sqlx(flatten)]
+    business: BusinessRow,
+    distance_from_route_meters: f64,
+    along_route_meters: f64,
+}
+
+impl From<RouteBusinessRow> for Business {
+    fn from(row: RouteBusinessRow) -> Self {
+        Self {
+            route_position: Some(RoutePosition {
+                distance_from_route_meters: row.distance_from_route_meters,
+                along_route_meters: row.along_route_meters,
+            }),
+            ..Business::from(row.business)
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct ClusterRow {
+    latitude: f64,
+    longitude: f64,
+    business_count: i64,
+    categories: Json<BTreeMap<String, i64>>,
+}
+
+impl From<ClusterRow> for BusinessCluster {
+    fn from(row: ClusterRow) -> Self {
+        Self {
+            latitude: row.latitude,
+            longitude: row.longitude,
+            business_count: row.business_count,
+            categories: row.categories.0
+                .into_iter()
+                .filter_map(|(category, count)| Some((category.parse().ok()?, count)))
+                .collect(),
+        }
+    }
+}
+
+const CITY_COLUMNS: &str =
+    "slug, name, name_cyrillic, name_latin, latitude, longitude, business_count, category_counts";
+
+#[derive(FromRow)]
+struct CityRow {
+    slug: String,
+    name: String,
+    name_cyrillic: Option<String>,
+    name_latin: String,
+    latitude: f64,
+    longitude: f64,
+    business_count: i64,
+    category_counts: Json<BTreeMap<String, i64>>,
+}
+
+impl From<CityRow> for City {
+    fn from(row: CityRow) -> Self {
+        Self {
+            slug: row.slug,
+            name: row.name,
+            name_cyrillic: row.name_cyrillic,
+            name_latin: row.name_latin,
+            latitude: row.latitude,
+            longitude: row.longitude,
+            business_count: row.business_count,
+            categories: row.category_counts.0
+                .into_iter()
+                .filter_map(|(category, count)| Some((category.parse().ok()?, count)))
+                .collect(),
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct AutocompleteRow {
+    kind: String,
+    id: Option<Uuid>,
+    text: String,
+    city: Option<String>,
+    business_count: Option<i64>,
+}
+
+impl AutocompleteRow {
+    fn into_suggestion(self) -> Option<AutocompleteSuggestion> {
+        match self.kind.as_str() {
+            "business" => Some(AutocompleteSuggestion::Business {
+                id: self.id?,
+                text: self.text,
+                city: self.city,
+            }),
+            "city" => Some(AutocompleteSuggestion::City {
+                text: self.text,
+                business_count: self.business_count.unwrap_or(0),
+            }),
+            "specialization" => Some(AutocompleteSuggestion::Specialization {
+                text: self.text,
+                business_count: self.business_count.unwrap_or(0),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Country polygon bound as $1, widened by 1 km so businesses on a border
+/// river or the shore still count as inside
+const COUNTRY_CTE: &str =
+    "country AS (SELECT ST_Buffer(ST_GeomFromText($1, 4326)::geography, 1000)::geometry AS geom)";
+
+/// Radius search totals are counted up to this many matches
+const MAX_TOTAL_ESTIMATE: i64 = 10_000;
+
+/// Nearest-neighbour candidates scanned per returned business before
+/// reordering by geodesic distance
+const KNN_OVERFETCH: i64 = 3;
+
+/// Search document expression; must match the text search indexes
+const SEARCH_DOCUMENT: &str =
+    "search.business_search_text(b.name, b.name_en, b.address, b.city, b.specializations)";
+
+/// Share of the text search ranking that comes from text relevance when a
+/// location is given; the rest rewards proximity
+const TEXT_RELEVANCE_WEIGHT: f64 = 0.7;
+
+/// Distance at which the proximity part of the text ranking drops to 1/e
+const DISTANCE_DECAY_METERS: f64 = 10_000.0;
+
+/// Business row for runtime-built queries. Categories are read as text since
+/// the enum array can only be decoded by the compile-time checked macros.
+#[derive(FromRow)]
+struct BusinessRow {
+    id: Uuid,
+    osm_id: Option<i64>,
+    name: Option<String>,
+    name_en: Option<String>,
+    address: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    categories: Vec<String>,
+    specializations: Option<Vec<String>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    logo_map_url: Option<String>,
+    is_registered: bool,
+    city: Option<String>,
+    average_reviews: f64,
+    review_count: i32,
+}
+
+impl From<BusinessRow> for Business {
+    fn from(row: BusinessRow) -> Self {
+        Self {
+            id: row.id,
+            osm_id: row.osm_id,
+            name: row.name,
+            name_en: row.name_en,
+            address: row.address,
+            latitude: row.latitude.unwrap_or(0.0),
+            longitude: row.longitude.unwrap_or(0.0),
+            categories: row.categories
+                .iter()
+                .filter_map(|c| c.parse().ok())
+                .collect(),
+            specializations: Some(row.specializations.unwrap_or_default()),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            logo_map_url: row.logo_map_url,
+            is_registered: row.is_registered,
+            city: row.city,
+            average_reviews: row.average_reviews,
+            review_count: row.review_count,
+            proximity: None,
+            route_position: None,
+        }
+    }
+}
+
+/// SQL condition matching `b.categories` against a text array parameter. An
+/// empty array matches everything; the cast keeps the GIN index usable.
+fn category_predicate(mode: MatchMode, param: &str) -> String {
+    let operator = match mode {
+        MatchMode::Any => "&&",
+        MatchMode::All => "@>",
+    };
+    format!(
+        "(cardinality({0}::text[]) = 0 OR b.categories {1} {0}::text[]::search.business_category[])",
+        param, operator
+    )
+}
+
+/// SQL condition applying an attribute filter to `b`, bound as four parameters
+/// from `$first`: minimum rating, minimum reviews, registered only and the
+/// specializations array (empty matches everything). The registration clause
+/// is written into the SQL rather than read from its parameter, so a generic
+/// plan can still use the partial index on registered businesses; the
+/// parameter stays bound to keep the numbering of the ones after it.
+fn attribute_predicate(filter: &AttributeFilter, first: usize) -> String {
+    let operator = match filter.specialization_mode {
+        MatchMode::Any => "&&",
+        MatchMode::All => "@>",
+    };
+    let registered = if filter.registered_only { " AND b.is_registered" } else { "" };
+    format!(
+        "(${0}::numeric IS NULL OR b.average_reviews >= ${0}::numeric) \
+         AND (${1}::int IS NULL OR b.review_count >= ${1}::int){2} \
+         AND (cardinality(${3}::text[]) = 0 OR b.specializations {4} ${3}::text[])",
+        first,
+        first + 1,
+        registered,
+        first + 3,
+        operator
+    )
+}
+
+/// `distance_meters` and `bearing_degrees` columns of `b` relative to the point
+/// given by latitude and longitude parameters. Both are NULL without a point.
+fn proximity_columns(latitude_param: &str, longitude_param: &str) -> String {
+    let point = format!(
+        "ST_SetSRID(ST_MakePoint({}, {}), 4326)::geography",
+        longitude_param, latitude_param
+    );
+    format!(
+        "ST_Distance(b.location::geography, {0}) AS distance_meters, \
+         COALESCE(degrees(ST_Azimuth({0}, b.location::geography)), 0) AS bearing_degrees",
+        point
+    )
+}
+
+/// Ascending sort key expression over `matched` (`BUSINESS_COLUMNS` plus
+/// `proximity_columns`) and its SQL type. Descending orders are negated so
+/// keyset pagination always compares with `>`. Ranking weights are bound as
+/// $12 rating prior mean, $13 prior reviews, $14-$16 distance, rating and
+/// registration weights and $17 the distance decay.
+fn sort_key(sort: SortOrder) -> (String, &'static str) {
+    let bayesian_rating = "COALESCE(\
+        ($12 * $13 + matched.average_reviews * matched.review_count) \
+        / NULLIF($13 + matched.review_count, 0), 0)";
+
+    match sort {
+        SortOrder::Distance => ("matched.distance_meters".to_string(), "float8"),
+        SortOrder::Rating => (format!("-{}", bayesian_rating), "float8"),
+        SortOrder::Reviews => ("-matched.review_count::float8".to_string(), "float8"),
+        SortOrder::Name => (
+            "CASE WHEN NULLIF(btrim(matched.name), '') IS NULL THEN '1' \
+             ELSE '0' || lower(matched.name) END"
+                .to_string(),
+            "text",
+        ),
+        SortOrder::BestMatch => (
+            format!(
+                "-($14 * exp(-matched.distance_meters / $17) \
+                 + $15 * {} / 5.0 \
+                 + $16 * matched.is_registered::int)",
+                bayesian_rating
+            ),
+            "float8",
+        ),
+    }
+}
+
+/// SQL condition for a data quality issue, over `search.businesses b` joined with
+/// `country(geom)` and with the stale period in days bound as `$2`
+fn issue_predicate(issue: DataQualityIssue) -> &'static str {
+    match issue {
+        DataQualityIssue::MissingName => "NULLIF(btrim(b.name), '') IS NULL",
+        DataQualityIssue::MissingAddress => "NULLIF(btrim(b.address), '') IS NULL",
+        DataQualityIssue::MissingCity => "NULLIF(btrim(b.city), '') IS NULL",
+        DataQualityIssue::MissingContact => {
+            "NULLIF(btrim(b.phone), '') IS NULL AND NULLIF(btrim(b.email), '') IS NULL AND NULLIF(btrim(b.website), '') IS NULL"
+        }
+        DataQualityIssue::MissingOpeningHours => "NULLIF(btrim(b.opening_hours), '') IS NULL",
+        DataQualityIssue::OutsideCountry => {
+            "NOT ST_IsEmpty(b.location) AND NOT ST_Intersects(b.location, country.geom)"
+        }
+        DataQualityIssue::ZeroCoordinates => {
+            "(ST_IsEmpty(b.location) OR (abs(ST_X(b.location)) < 1e-6 AND abs(ST_Y(b.location)) < 1e-6))"
+        }
+        DataQualityIssue::SwappedCoordinates => {
+            "NOT ST_IsEmpty(b.location) AND NOT ST_Intersects(b.location, country.geom) AND ST_Intersects(ST_FlipCoordinates(b.location), country.geom)"
+        }
+        DataQualityIssue::Stale => "b.updated_at < NOW() - make_interval(days => $2)",
+    }
+}
+
+/// Smallest string sorting after every string that starts with `prefix`, the
+/// exclusive end of a prefix range scan. None for an empty prefix.
+fn prefix_upper_bound(prefix: &str) -> Option<String> {
+    let mut upper = prefix.to_string();
+    let last = upper.pop()?;
+    upper.push(char::from_u32(last as u32 + 1)?);
+    Some(upper)
+}
+
+fn data_quality_counts(row: &PgRow) -> Result<DataQualityCounts> {
+    let mut issues = BTreeMap::new();
+    for issue in DataQualityIssue::iter() {
+        issues.insert(issue, row.try_get::<i64, _>(issue.to_string().as_str())?);
+    }
+
+    Ok(DataQualityCounts {
+        total: row.try_get("total")?,
+        issues,
+    })
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::entities::category::{BusinessCategory, ANY_TAG_VALUE};
 use crate::shared::error::AppError;
 
 #[derive(Debug, Clone)]
@@ -78,29 +79,27 @@ impl OverpassService {
 }
 
 impl OverpassQuery {
+    /// Query for every node carrying a tag the category classifier understands
+    /// or named with a keyword it infers a category from
     pub fn car_related_businesses(country_code: &str) -> Self {
+        let mut filters: String = BusinessCategory::osm_tag_filters()
+            .into_iter()
+            .map(|(key, values)| Self::tag_filter(key, &values))
+            .collect();
+        filters.push_str(&Self::name_filter(BusinessCategory::name_keywords()));
+
         let query = format!(
             r#"[out:json][timeout:50];
 area["ISO3166-1"="{}"][admin_level=2]->.searchArea;
 
 // Nodes for car-related amenities
 (
-  node["amenity"="car_wash"](area.searchArea);
-  node["amenity"="fuel"](area.searchArea);
-  node["amenity"="charging_station"](area.searchArea);
-  node["amenity"="car_rental"](area.searchArea);
-  node["amenity"="parking"](area.searchArea);
-  node["shop"="car_repair"](area.searchArea);
-  node["shop"="car"](area.searchArea);
-  node["shop"="car_parts"](area.searchArea);
-  node["shop"="tyres"](area.searchArea);
-  node["craft"="car_repair"](area.searchArea);
-  node["service"="vehicle_inspection"](area.searchArea);
-);
+{});
 out body;
 >;
 out skel qt;"#,
-            country_code
+            country_code,
+            filters
         );
 
         Self {
@@ -109,6 +108,41 @@ out skel qt;"#,
         }
     }
 
+    /// Matches any of `values`, including inside `;`-separated multi-values
+    /// with whitespace around the separator (`car; car_repair`). The POSIX
+    /// class avoids a `\s` that would need escaping twice.
+    fn tag_filter(key: &str, values: &[&str]) -> String {
+        if values.contains(&ANY_TAG_VALUE) {
+            return format!("  node[\"{0}\"][\"{0}\"!=\"no\"](area.searchArea);\n", key);
+        }
+
+        format!(
+            "  node[\"{}\"~\"(^|;)[[:space:]]*({})[[:space:]]*(;|$)\"](area.searchArea);\n",
+            key,
+            values.join("|")
+        )
+    }
+
+    /// Matches names with a word starting with any of `keywords`. Overpass case
+    /// folding can't be relied on for Cyrillic, so the first letter of each
+    /// keyword is matched in both cases to catch capitalized names; the word
+    /// boundary uses ASCII classes for the same reason.
+    fn name_filter(keywords: &[&str]) -> String {
+        let pattern: Vec<String> = keywords
+            .iter()
+            .filter_map(|keyword| {
+                let mut chars = keyword.chars();
+                let first = chars.next()?;
+                Some(format!("[{}{}]{}", first.to_uppercase(), first, chars.as_str()))
+            })
+            .collect();
+
+        format!(
+            "  node[\"name\"~\"(^|[[:space:][:punct:]])({})\",i](area.searchArea);\n",
+            pattern.join("|")
+        )
+    }
+
     pub fn custom(query: String, timeout: u64) -> Self {
         Self { query, timeout }
     }
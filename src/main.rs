@@ -8,7 +8,7 @@ use sqlx::migrate::Migrator;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use vroomgine::{application::{handlers::{business::{search_businesses_by_radius_and_category, sync_businesses, sync_user_business}, health::health_check}, state::AppState}, config::config::Config, infrastructure::database::create_pool};
+use vroomgine::{application::{handlers::{business::{autocomplete_businesses, cluster_businesses, get_business, get_business_batch, nearest_businesses_by_category, search_businesses_by_radius, search_businesses_by_radius_and_category, search_businesses_along_route, search_businesses_by_text, search_businesses_in_area, search_businesses_in_bbox, search_nearest_businesses, sync_businesses, sync_user_business}, cities::{list_businesses_in_city, list_cities}, data_quality::{get_data_quality_report, list_businesses_with_issue}, duplicates::{detect_duplicates, list_duplicate_candidates, merge_businesses}, health::health_check, tiles::get_business_tile}, state::AppState}, config::config::Config, infrastructure::database::create_pool};
 
 static MIGRATOR: Migrator = sqlx::migrate!();
 
@@ -43,7 +43,26 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .route("/health", get(health_check))
         .route("/businesses/sync", post(sync_businesses))
         .route("/businesses/sync", put(sync_user_business))
+        .route("/businesses/batch", post(get_business_batch))
+        .route("/businesses/{id}", get(get_business))
+        .route("/businesses/search", get(search_businesses_by_text))
+        .route("/businesses/search/bbox", get(search_businesses_in_bbox))
+        .route("/businesses/search/route", post(search_businesses_along_route))
+        .route("/businesses/search/area", post(search_businesses_in_area))
+        .route("/businesses/search/nearest", get(search_nearest_businesses))
+        .route("/businesses/nearest-by-category", get(nearest_businesses_by_category))
+        .route("/businesses/clusters", get(cluster_businesses))
+        .route("/businesses/autocomplete", get(autocomplete_businesses))
         .route("/businesses/search/radius-category", get(search_businesses_by_radius_and_category))
+        .route("/businesses/search/radius", get(search_businesses_by_radius))
+        .route("/tiles/businesses/{z}/{x}/{y}", get(get_business_tile))
+        .route("/cities", get(list_cities))
+        .route("/cities/{slug}/businesses", get(list_businesses_in_city))
+        .route("/admin/duplicates", get(list_duplicate_candidates))
+        .route("/admin/duplicates/detect", post(detect_duplicates))
+        .route("/admin/duplicates/merge", post(merge_businesses))
+        .route("/admin/data-quality", get(get_data_quality_report))
+        .route("/admin/data-quality/{issue}", get(list_businesses_with_issue))
         .with_state(state)
         .layer(
             ServiceBuilder::new()
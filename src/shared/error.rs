@@ -25,6 +25,27 @@ pub enum AppError {
     
     #[error("Import error: {0}")]
     Import(String),
+
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid {field}: {message}")]
+    Validation { field: String, message: String },
+}
+
+impl AppError {
+    pub fn validation(field: &str, message: impl Into<String>) -> Self {
+        AppError::Validation {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
 }
 
 impl IntoResponse for AppError {
@@ -54,12 +75,32 @@ impl IntoResponse for AppError {
                 tracing::error!("Import error: {}", err);
                 (StatusCode::INTERNAL_SERVER_ERROR, "Import failed")
             }
+            AppError::BadRequest(ref err) => {
+                tracing::warn!("Bad request: {}", err);
+                (StatusCode::BAD_REQUEST, "Invalid request")
+            }
+            AppError::Conflict(ref err) => {
+                tracing::warn!("Conflict: {}", err);
+                (StatusCode::CONFLICT, "Conflicting request")
+            }
+            AppError::NotFound(ref err) => {
+                tracing::debug!("Not found: {}", err);
+                (StatusCode::NOT_FOUND, "Resource not found")
+            }
+            AppError::Validation { ref field, ref message } => {
+                tracing::warn!("Invalid parameter {}: {}", field, message);
+                (StatusCode::BAD_REQUEST, "Invalid parameter")
+            }
         };
 
-        let body = Json(json!({
+        let mut body = json!({
             "error": error_message,
             "details": self.to_string()
-        }));
+        });
+        if let AppError::Validation { field, .. } = &self {
+            body["field"] = json!(field);
+        }
+        let body = Json(body);
 
         (status, body).into_response()
     }